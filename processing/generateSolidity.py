@@ -732,13 +732,14 @@ def generate_solidity_script(mons: Dict[str, MonData], contracts: Dict[str, Cont
     return "\n".join(all_lines)
 
 
-def run(include_color: bool = False, base_path: str = ".") -> bool:
+def run(include_color: bool = False, base_path: str = ".", check: bool = False) -> bool:
     """
     Run Solidity generation. Returns True on success, False on failure.
 
     Args:
         include_color: Whether to include sprite and palette color data
         base_path: Base path to the repository root
+        check: Don't write; fail if the committed script differs from what the CSVs generate
     """
     try:
         # Read CSV data
@@ -756,6 +757,10 @@ def run(include_color: bool = False, base_path: str = ".") -> bool:
 
         # Write to output file
         output_path = os.path.join(base_path, "script", "SetupMons.s.sol")
+
+        if check:
+            return check_output(output_path, solidity_code)
+
         os.makedirs(os.path.dirname(output_path), exist_ok=True)
 
         with open(output_path, 'w', encoding='utf-8') as f:
@@ -791,6 +796,29 @@ def run(include_color: bool = False, base_path: str = ".") -> bool:
         return False
 
 
+def check_output(output_path: str, solidity_code: str) -> bool:
+    """Compare the committed deployment script against freshly generated code."""
+    import difflib
+
+    existing = ""
+    if os.path.exists(output_path):
+        with open(output_path, 'r', encoding='utf-8') as f:
+            existing = f.read()
+    if existing == solidity_code:
+        print(f"{output_path} matches the CSV data")
+        return True
+
+    diff = difflib.unified_diff(
+        existing.splitlines(keepends=True),
+        solidity_code.splitlines(keepends=True),
+        fromfile=f"{output_path} (committed)",
+        tofile=f"{output_path} (from drool/*.csv)",
+    )
+    print("".join(diff), end="")
+    print(f"\n{output_path} is stale; rerun generateSolidity.py")
+    return False
+
+
 def main():
     """CLI entry point."""
     import sys
@@ -799,9 +827,11 @@ def main():
     parser = argparse.ArgumentParser(description='Generate Solidity deployment script for mons')
     parser.add_argument('--color', action='store_true',
                        help='Include sprite and palette color data in the generated script')
+    parser.add_argument('--check', action='store_true',
+                       help='Verify script/SetupMons.s.sol matches the CSV data without writing it')
     args = parser.parse_args()
 
-    if not run(include_color=args.color):
+    if not run(include_color=args.color, check=args.check):
         sys.exit(1)
 
 
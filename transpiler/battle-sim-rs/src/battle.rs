@@ -0,0 +1,403 @@
+//! The scripted-battle harness: fields two teams from a local deployment's mon registry through a
+//! freshly deployed `TestTeamRegistry`, starts a battle on the deployed Engine with the harness as
+//! matchmaker and move manager, and plays turns through `executeWithMoves` /
+//! `executeWithSingleMove`, reading state back through `getBattle`.
+
+use crate::chain::{artifact_bytecode, Chain, Error, Result};
+use crate::script::{Action, MonPick, Script};
+use alloy_primitives::aliases::U104;
+use alloy_primitives::{Address, FixedBytes, U256};
+use chomp_bindings::{
+    Battle as BattleConfig, BattleData, IEffect, IEngine, IMoveSet, ITeamRegistry, Mon, TestTeamRegistry,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// `Constants.sol`'s `INLINE_STAMINA_REGEN_RULESET`: stamina regen without a ruleset contract.
+pub const INLINE_STAMINA_REGEN_RULESET: Address =
+    Address::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x05, 0x7a]);
+/// `Constants.sol`'s `CLEARED_MON_STATE_SENTINEL`: a delta never written since the slot was reset.
+const CLEARED_MON_STATE_SENTINEL: i32 = i32::MAX - 1;
+/// Moves the engine stores per mon (`GAME_MOVES_PER_MON`).
+pub const MOVES_PER_MON: usize = 4;
+/// Bit 160 of a move word tags it as carrying metadata rather than inline move data.
+const MOVE_META_TAG_BIT: usize = 160;
+
+/// The harness's made-up accounts: the two players, and the operator that deploys the test
+/// registry and acts as matchmaker and move manager.
+pub const P0: Address = Address::repeat_byte(0xa0);
+pub const P1: Address = Address::repeat_byte(0xb0);
+pub const OPERATOR: Address = Address::repeat_byte(0x5e);
+
+/// Contract names by address, from the `.env` that `processing/deploy.py` writes
+/// (`ENGINE=0x…`, `GACHA_TEAM_REGISTRY=0x…`, one line per deployed contract).
+#[derive(Clone, Debug, Default)]
+pub struct AddressBook {
+    by_name: BTreeMap<String, Address>,
+    by_address: HashMap<Address, String>,
+}
+
+impl AddressBook {
+    pub fn parse(text: &str) -> AddressBook {
+        let mut book = AddressBook::default();
+        for line in text.lines().map(str::trim).filter(|l| !l.starts_with('#')) {
+            let Some((name, value)) = line.split_once('=') else { continue };
+            let Ok(address) = value.trim().trim_matches('"').parse::<Address>() else { continue };
+            book.by_address.entry(address).or_insert_with(|| name.trim().to_string());
+            book.by_name.insert(name.trim().to_string(), address);
+        }
+        book
+    }
+
+    pub fn address(&self, name: &str) -> Option<Address> {
+        self.by_name.get(name).copied()
+    }
+
+    pub fn name_of(&self, address: Address) -> Option<&str> {
+        self.by_address.get(&address).map(String::as_str)
+    }
+}
+
+/// Mon names by registry id, from `drool/mons.csv` (`Id,Name,…`).
+pub fn load_mon_names(path: &Path) -> std::result::Result<HashMap<u64, String>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(text
+        .lines()
+        .skip(1)
+        .filter_map(|l| {
+            let mut fields = l.splitn(3, ',');
+            Some((fields.next()?.parse().ok()?, fields.next()?.to_string()))
+        })
+        .collect())
+}
+
+/// What a battle is played against: the deployed Engine, the registry mon data comes from, the
+/// test registry's creation code, the ruleset, and the names to print.
+pub struct Setup {
+    pub engine: Address,
+    pub mon_registry: Address,
+    pub test_registry_code: Vec<u8>,
+    pub ruleset: Address,
+    pub book: AddressBook,
+    pub mon_names: HashMap<u64, String>,
+}
+
+impl Setup {
+    /// Reads the address book (`addresses`) and the test registry artifact (under `artifacts`);
+    /// `engine` / `registry` override the book's `ENGINE` / `GACHA_TEAM_REGISTRY`, and `ruleset`
+    /// is `inline` (the default), `none`, an address or a book name.
+    pub fn load(
+        addresses: &Path,
+        artifacts: &Path,
+        engine: Option<Address>,
+        registry: Option<Address>,
+        ruleset: Option<&str>,
+    ) -> std::result::Result<Setup, String> {
+        let read = |p: &Path| std::fs::read_to_string(p).map_err(|e| format!("{}: {e}", p.display()));
+        let book = AddressBook::parse(&read(addresses)?);
+        let named = |over: Option<Address>, name: &str| {
+            over.or_else(|| book.address(name)).ok_or_else(|| format!("no {name} in {}", addresses.display()))
+        };
+        let ruleset = match ruleset {
+            None | Some("inline") => INLINE_STAMINA_REGEN_RULESET,
+            Some("none") => Address::ZERO,
+            Some(v) => v.parse().ok().or_else(|| book.address(v)).ok_or_else(|| format!("unknown ruleset {v}"))?,
+        };
+        let artifact = artifacts.join("TestTeamRegistry.sol").join("TestTeamRegistry.json");
+        Ok(Setup {
+            engine: named(engine, "ENGINE")?,
+            mon_registry: named(registry, "GACHA_TEAM_REGISTRY")?,
+            test_registry_code: artifact_bytecode(&read(&artifact)?)
+                .map_err(|e| format!("{}: {e}", artifact.display()))?,
+            ruleset,
+            mon_names: load_mon_names(&crate::chomp_root().join("drool").join("mons.csv"))?,
+            book,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonView {
+    pub id: u64,
+    pub name: String,
+    pub hp: i64,
+    pub max_hp: u32,
+    pub stamina: i64,
+    pub max_stamina: u32,
+    pub knocked_out: bool,
+    pub moves: Vec<String>,
+    pub effects: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SideView {
+    pub active: usize,
+    pub mons: Vec<MonView>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    pub turn: u64,
+    /// 0 or 1 once the battle is over.
+    pub winner: Option<usize>,
+    /// Whose action the next turn takes.
+    pub to_move: [bool; 2],
+    pub global_effects: Vec<String>,
+    pub sides: [SideView; 2],
+}
+
+pub struct Battle {
+    pub chain: Chain,
+    pub engine: Address,
+    pub key: FixedBytes<32>,
+    /// Registry ids per side, in team order.
+    ids: [Vec<u64>; 2],
+    book: AddressBook,
+    mon_names: HashMap<u64, String>,
+    names: HashMap<Address, String>,
+}
+
+impl Battle {
+    /// Deploys a test registry holding both teams and starts a battle between them.
+    pub fn start(mut chain: Chain, setup: &Setup, teams: [&[MonPick]; 2]) -> Result<Battle> {
+        let registry = chain.deploy(OPERATOR, setup.test_registry_code.clone())?;
+        for (player, picks) in [P0, P1].into_iter().zip(teams) {
+            let mut team = Vec::with_capacity(picks.len());
+            for pick in picks {
+                team.push(registry_mon(&mut chain, setup.mon_registry, pick)?);
+            }
+            chain.transact(OPERATOR, registry, TestTeamRegistry::setTeamCall { player, team })?;
+            let call = IEngine::updateMatchmakersCall { makersToAdd: vec![OPERATOR], makersToRemove: vec![] };
+            chain.transact(player, setup.engine, call)?;
+        }
+        let battle = BattleConfig {
+            p0: P0,
+            p0TeamIndex: Default::default(),
+            p1: P1,
+            p1TeamIndex: Default::default(),
+            p2: Address::ZERO,
+            p2TeamIndex: Default::default(),
+            p3: Address::ZERO,
+            p3TeamIndex: Default::default(),
+            teamRegistry: registry,
+            rngOracle: Address::ZERO,
+            ruleset: setup.ruleset,
+            moveManager: OPERATOR,
+            matchmaker: OPERATOR,
+            engineHooks: vec![],
+        };
+        chain.transact(OPERATOR, setup.engine, IEngine::startBattleCall { battle })?;
+        let key = chain.view(setup.engine, IEngine::computeBattleKeyCall { p0: P0, p1: P1 })?.battleKey;
+        let ids = teams.map(|picks| picks.iter().map(MonPick::id).collect());
+        Ok(Battle {
+            chain,
+            engine: setup.engine,
+            key,
+            ids,
+            book: setup.book.clone(),
+            mon_names: setup.mon_names.clone(),
+            names: HashMap::new(),
+        })
+    }
+
+    pub fn team_size(&self, side: usize) -> usize {
+        self.ids[side].len()
+    }
+
+    /// `[p0 acts, p1 acts]` for the next turn; both false once the battle is over.
+    pub fn to_move(&mut self) -> Result<[bool; 2]> {
+        let data = self.chain.view(self.engine, IEngine::getBattleCall { battleKey: self.key })?.data;
+        Ok(to_move(&data))
+    }
+
+    pub fn winner(&mut self) -> Result<Option<usize>> {
+        let data = self.chain.view(self.engine, IEngine::getBattleCall { battleKey: self.key })?.data;
+        Ok((data.winnerIndex < 2).then_some(data.winnerIndex as usize))
+    }
+
+    /// Plays one turn a block later; a side the engine isn't waiting on has its action dropped.
+    pub fn step(&mut self, actions: [Action; 2], salts: [u128; 2]) -> Result<()> {
+        let to_move = self.to_move()?;
+        self.chain.advance(1);
+        let [(m0, x0), (m1, x1)] = actions.map(Action::encode);
+        let salt = |s: u128| U104::from(s);
+        match to_move {
+            [true, true] => {
+                let call = IEngine::executeWithMovesCall {
+                    battleKey: self.key,
+                    p0MoveIndex: m0,
+                    p0Salt: salt(salts[0]),
+                    p0ExtraData: x0,
+                    p1MoveIndex: m1,
+                    p1Salt: salt(salts[1]),
+                    p1ExtraData: x1,
+                };
+                self.chain.transact(OPERATOR, self.engine, call)?;
+            }
+            [p0, p1] if p0 || p1 => {
+                let (move_index, s, extra_data) = if p0 { (m0, salts[0], x0) } else { (m1, salts[1], x1) };
+                let call = IEngine::executeWithSingleMoveCall {
+                    battleKey: self.key,
+                    moveIndex: move_index,
+                    salt: salt(s),
+                    extraData: extra_data,
+                };
+                self.chain.transact(OPERATOR, self.engine, call)?;
+            }
+            _ => return Err(Error::Other("battle is over".to_string())),
+        }
+        Ok(())
+    }
+
+    /// Whether the engine would accept `action` from `side` right now.
+    pub fn is_valid(&mut self, side: usize, action: Action) -> Result<bool> {
+        let (move_index, extra_data) = action.encode();
+        let call = IEngine::validatePlayerMoveForBattleCall {
+            battleKey: self.key,
+            moveIndex: U256::from(move_index),
+            playerIndex: U256::from(side),
+            extraData: extra_data,
+        };
+        self.chain.view(self.engine, call)
+    }
+
+    pub fn snapshot(&mut self) -> Result<Snapshot> {
+        let battle = self.chain.view(self.engine, IEngine::getBattleCall { battleKey: self.key })?;
+        let (config, data) = (battle.config, battle.data);
+        let active =
+            self.chain.view(self.engine, IEngine::getActiveMonIndexForBattleStateCall { battleKey: self.key })?;
+        let mut global_effects = Vec::new();
+        for e in &config.globalEffects {
+            global_effects.push(self.effect_name(e.effect));
+        }
+        let mut sides = Vec::with_capacity(2);
+        for side in 0..2 {
+            let effects = if side == 0 { &config.p0Effects } else { &config.p1Effects };
+            let mut mons = Vec::new();
+            for (i, mon) in config.teams.get(side).into_iter().flatten().enumerate() {
+                let state = config.monStates.get(side).and_then(|s| s.get(i));
+                let delta = |d: Option<i32>| match d {
+                    Some(CLEARED_MON_STATE_SENTINEL) | None => 0,
+                    Some(d) => d as i64,
+                };
+                let mut moves = Vec::with_capacity(mon.moves.len());
+                for word in mon.moves.iter().filter(|w| !w.is_zero()) {
+                    moves.push(self.move_name(*word));
+                }
+                let mut named = Vec::new();
+                for e in effects.get(i).into_iter().flatten() {
+                    named.push(self.effect_name(e.effect));
+                }
+                let id = self.ids[side].get(i).copied().unwrap_or_default();
+                mons.push(MonView {
+                    id,
+                    name: self.mon_names.get(&id).cloned().unwrap_or_else(|| format!("#{id}")),
+                    hp: mon.stats.hp as i64 + delta(state.map(|s| s.hpDelta)),
+                    max_hp: mon.stats.hp,
+                    stamina: mon.stats.stamina as i64 + delta(state.map(|s| s.staminaDelta)),
+                    max_stamina: mon.stats.stamina,
+                    knocked_out: state.is_some_and(|s| s.isKnockedOut),
+                    moves,
+                    effects: named,
+                });
+            }
+            let active = active.get(side).map_or(0, |a| a.saturating_to());
+            sides.push(SideView { active, mons });
+        }
+        let [p0, p1]: [SideView; 2] = sides.try_into().expect("two sides");
+        Ok(Snapshot {
+            turn: data.turnId as u64,
+            winner: (data.winnerIndex < 2).then_some(data.winnerIndex as usize),
+            to_move: to_move(&data),
+            global_effects,
+            sides: [p0, p1],
+        })
+    }
+
+    fn effect_name(&mut self, at: Address) -> String {
+        if let Some(n) = self.book.name_of(at).or(self.names.get(&at).map(String::as_str)) {
+            return n.to_string();
+        }
+        let name = self.chain.view(at, IEffect::nameCall {}).unwrap_or_else(|_| at.to_string());
+        self.names.insert(at, name.clone());
+        name
+    }
+
+    fn move_name(&mut self, word: U256) -> String {
+        let at = Address::from_word(word.to_be_bytes::<32>().into());
+        if !word.bit(MOVE_META_TAG_BIT) && word >> 160 != U256::ZERO {
+            return format!("inline {word:#x}");
+        }
+        if let Some(n) = self.book.name_of(at).or(self.names.get(&at).map(String::as_str)) {
+            return n.to_string();
+        }
+        let name = self.chain.view(at, IMoveSet::nameCall {}).unwrap_or_else(|_| at.to_string());
+        self.names.insert(at, name.clone());
+        name
+    }
+}
+
+fn to_move(data: &BattleData) -> [bool; 2] {
+    match (data.winnerIndex, data.playerSwitchForTurnFlag) {
+        (0 | 1, _) => [false, false],
+        (_, 0) => [true, false],
+        (_, 1) => [false, true],
+        _ => [true, true],
+    }
+}
+
+/// A registry mon as a battle loads it: stats and first ability from `getMonData`, and either
+/// the default loadout (the first four moves of its row) or the lanes `pick` selects.
+fn registry_mon(chain: &mut Chain, registry: Address, pick: &MonPick) -> Result<Mon> {
+    let data = chain.view(registry, ITeamRegistry::getMonDataCall { monId: U256::from(pick.id()) })?;
+    let row: Vec<U256> = data.moves.into_iter().filter(|w| !w.is_zero()).collect();
+    let moves = match pick {
+        MonPick::Id(_) => row.iter().take(MOVES_PER_MON).copied().collect(),
+        MonPick::Loadout { id, moves } => moves
+            .iter()
+            .map(|&lane| {
+                row.get(lane).copied().ok_or_else(|| Error::Other(format!("mon {id} has no move lane {lane}")))
+            })
+            .collect::<Result<Vec<_>>>()?,
+    };
+    Ok(Mon { stats: data.mon, ability: data.abilities.first().copied().unwrap_or_default(), moves })
+}
+
+/// One played turn as the simulator reports it.
+#[derive(Clone, Debug, Serialize)]
+pub struct Turn {
+    /// The actions the engine took; `None` for a side it wasn't waiting on.
+    pub actions: [Option<Action>; 2],
+    pub state: Snapshot,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Replay {
+    pub start: Snapshot,
+    pub turns: Vec<Turn>,
+    /// Why the script stopped early (a reverted turn), if it did.
+    pub error: Option<String>,
+}
+
+/// Plays `script` to its end or the battle's, whichever comes first.
+pub fn play(chain: Chain, setup: &Setup, script: &Script) -> Result<(Battle, Replay)> {
+    let mut battle = Battle::start(chain, setup, [&script.p0, &script.p1])?;
+    let start = battle.snapshot()?;
+    let mut replay = Replay { start, turns: Vec::new(), error: None };
+    for (i, actions) in script.turns.iter().enumerate() {
+        let to_move = battle.to_move()?;
+        if to_move == [false, false] {
+            break;
+        }
+        if let Err(e) = battle.step(*actions, [script.salt(i, 0), script.salt(i, 1)]) {
+            replay.error = Some(format!("turn {i}: {e}"));
+            break;
+        }
+        let played = [0, 1].map(|side| to_move[side].then_some(actions[side]));
+        replay.turns.push(Turn { actions: played, state: battle.snapshot()? });
+    }
+    Ok((battle, replay))
+}
@@ -0,0 +1,108 @@
+//! Invariant fuzzer — plays random battles (random registry teams, random engine-accepted actions)
+//! against the real Engine bytecode under revm and checks HP bounds, stamina accounting and that
+//! every battle keeps moving and ends. Failing cases shrink and land as battle scripts:
+//!   cargo run --release --bin battle-fuzz -- --state state.json [--cases 100] [--seed 1] \
+//!       [--team-size 4] [--max-turns 200] [--out-dir fuzz-failures] [--artifacts out] [--addresses .env] \
+//!       [--ruleset inline|none|0x…|NAME] [--engine 0x…] [--registry 0x…]
+//!   cargo run --release --bin battle-sim -- --state state.json --script fuzz-failures/case-<seed>.json
+//! Same `--seed`, same cases. Exits 1 when any case fails.
+
+use alloy_primitives::{Address, U256};
+use chomp_battle_sim::battle::Setup;
+use chomp_battle_sim::chain::Chain;
+use chomp_battle_sim::chomp_root;
+use chomp_battle_sim::fuzz::{explore, replay, shrink, Rng};
+use chomp_bindings::ITeamRegistry;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: battle-fuzz --state <anvil state.json> [--cases N] [--seed N] [--team-size N] \
+                     [--max-turns N] [--out-dir <dir>] [--artifacts <foundry out dir>] [--addresses <.env>] \
+                     [--ruleset inline|none|0x…|NAME] [--engine 0x…] [--registry 0x…]";
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("battle-fuzz: {msg}\n{USAGE}");
+    std::process::exit(2);
+}
+
+fn arg_n(args: &[String], flag: &str) -> Option<u64> {
+    arg(args, flag).map(|v| v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not a number: {v}"))))
+}
+
+fn path(args: &[String], flag: &str, default: &str) -> PathBuf {
+    chomp_root().join(arg(args, flag).unwrap_or_else(|| default.to_string()))
+}
+
+fn parse_addr(args: &[String], flag: &str) -> Option<Address> {
+    arg(args, flag).map(|v| v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not an address: {v}"))))
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(0) => {}
+        Ok(failures) => {
+            eprintln!("battle-fuzz: {failures} failing cases");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("battle-fuzz: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<usize> {
+    let state = arg(args, "--state").unwrap_or_else(|| fail("missing --state"));
+    let cases = arg_n(args, "--cases").unwrap_or(100);
+    let seed = arg_n(args, "--seed").unwrap_or(1);
+    let team_size = arg_n(args, "--team-size").unwrap_or(4) as usize;
+    let max_turns = arg_n(args, "--max-turns").unwrap_or(200) as usize;
+    let out_dir = path(args, "--out-dir", "fuzz-failures");
+    let setup = Setup::load(
+        &path(args, "--addresses", ".env"),
+        &path(args, "--artifacts", "out"),
+        parse_addr(args, "--engine"),
+        parse_addr(args, "--registry"),
+        arg(args, "--ruleset").as_deref(),
+    )?;
+
+    let mut base = Chain::from_anvil_state(&std::fs::read_to_string(chomp_root().join(state))?)?;
+    let all = ITeamRegistry::getMonIdsCall { start: U256::ZERO, end: U256::ZERO };
+    let mon_ids: Vec<u64> = base.view(setup.mon_registry, all)?.iter().map(|id| id.saturating_to()).collect();
+    if mon_ids.is_empty() {
+        fail("the registry has no mons");
+    }
+
+    let mut rng = Rng::new(seed);
+    let mut failures = 0;
+    for case in 0..cases {
+        let Some(found) = explore(&base, &setup, &mut rng, &mon_ids, team_size, max_turns)? else { continue };
+        failures += 1;
+        let turns = found.script.turns.len();
+        eprintln!("battle-fuzz: case {case} (seed {}): {} at turn {turns}", found.script.seed, found.violation);
+        let mut small = shrink(
+            &found.script,
+            |s| matches!(replay(&base, &setup, s, max_turns), Ok(Some(v)) if v.same_kind(&found.violation)),
+        );
+        let violation = replay(&base, &setup, &small, max_turns)?.unwrap_or(found.violation);
+        small.note = Some(violation.to_string());
+        std::fs::create_dir_all(&out_dir)?;
+        let out = out_dir.join(format!("case-{}.json", small.seed));
+        std::fs::write(&out, serde_json::to_string_pretty(&small)? + "\n")?;
+        eprintln!(
+            "battle-fuzz:   shrunk to {} turns, {}v{} mons: {violation} -> {}",
+            small.turns.len(),
+            small.p0.len(),
+            small.p1.len(),
+            out.display()
+        );
+    }
+    eprintln!("battle-fuzz: {cases} cases, {failures} failing");
+    Ok(failures)
+}
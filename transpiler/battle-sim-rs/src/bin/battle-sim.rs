@@ -0,0 +1,80 @@
+//! Battle simulator — plays a scripted battle (see `chomp_battle_sim::script`) against the real
+//! Engine bytecode under revm, no node needed, and writes every turn's state as JSON:
+//!   anvil --dump-state state.json &   # then: python processing/deploy.py against it, stop anvil
+//!   cargo run --release --bin battle-sim -- --state state.json --script battle.json [--out replay.json] \
+//!       [--artifacts out] [--addresses .env] [--ruleset inline|none|0x…|NAME] [--engine 0x…] [--registry 0x…]
+//! Addresses resolve by name from the `.env` deploy.py writes (`ENGINE`, `GACHA_TEAM_REGISTRY`);
+//! teams are fielded through a fresh `TestTeamRegistry` from the Foundry artifacts, with stats and
+//! moves read from the deployed registry's `getMonData`. Relative paths resolve under CHOMP_ROOT.
+
+use alloy_primitives::Address;
+use chomp_battle_sim::battle::{play, Setup};
+use chomp_battle_sim::chain::Chain;
+use chomp_battle_sim::chomp_root;
+use chomp_battle_sim::script::Script;
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: battle-sim --state <anvil state.json> --script <battle.json> [--out <file>] \
+                     [--artifacts <foundry out dir>] [--addresses <.env>] [--ruleset inline|none|0x…|NAME] \
+                     [--engine 0x…] [--registry 0x…]";
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("battle-sim: {msg}\n{USAGE}");
+    std::process::exit(2);
+}
+
+fn path(args: &[String], flag: &str, default: &str) -> PathBuf {
+    chomp_root().join(arg(args, flag).unwrap_or_else(|| default.to_string()))
+}
+
+fn parse_addr(args: &[String], flag: &str) -> Option<Address> {
+    arg(args, flag).map(|v| v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not an address: {v}"))))
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args) {
+        eprintln!("battle-sim: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let state = arg(args, "--state").unwrap_or_else(|| fail("missing --state"));
+    let script = arg(args, "--script").unwrap_or_else(|| fail("missing --script"));
+    let script = Script::parse(&std::fs::read_to_string(chomp_root().join(script))?)?;
+    let setup = Setup::load(
+        &path(args, "--addresses", ".env"),
+        &path(args, "--artifacts", "out"),
+        parse_addr(args, "--engine"),
+        parse_addr(args, "--registry"),
+        arg(args, "--ruleset").as_deref(),
+    )?;
+
+    let chain = Chain::from_anvil_state(&std::fs::read_to_string(chomp_root().join(state))?)?;
+    let (_, replay) = play(chain, &setup, &script)?;
+    let last = replay.turns.last().map_or(&replay.start, |t| &t.state);
+    let summary = match (last.winner, &replay.error) {
+        (_, Some(e)) => format!("stopped at {e}"),
+        (Some(w), None) => format!("p{w} wins on turn {}", last.turn),
+        (None, None) => format!("no winner after turn {}", last.turn),
+    };
+    let text = serde_json::to_string_pretty(&replay)? + "\n";
+    match arg(args, "--out") {
+        Some(out) => {
+            std::fs::write(&out, text)?;
+            eprintln!("battle-sim: {} turns, {summary} -> {out}", replay.turns.len());
+        }
+        None => {
+            print!("{text}");
+            eprintln!("battle-sim: {} turns, {summary}", replay.turns.len());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,232 @@
+//! revm over a local deployment: an in-memory chain seeded from an anvil state dump, with typed
+//! transactions and views through the [`chomp_bindings`] declarations.
+//!
+//! revm 10 speaks alloy-primitives 0.7 while the bindings speak 1.x, so addresses and words cross
+//! the boundary as bytes. Every transaction runs at gas price 0 with no nonce check, so the
+//! harness's made-up senders need neither funds nor an account.
+
+use alloy_primitives::{hex, Address, U256};
+use chomp_bindings::alloy_sol_types::{decode_revert_reason, SolCall, SolInterface};
+use chomp_bindings::IEngine::IEngineErrors;
+use revm::db::{CacheDB, EmptyDB};
+use revm::primitives::{self as rp, AccountInfo, Bytecode, ExecutionResult, Output, SpecId, TxKind, KECCAK_EMPTY};
+use revm::Evm;
+use serde_json::Value;
+
+/// Gas for one transaction; a full engine turn with every hook firing stays far below this.
+pub const TX_GAS: u64 = 1_000_000_000;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The call reverted; the payload is the raw revert data.
+    Reverted(Vec<u8>),
+    Halted(String),
+    /// A malformed state file or artifact, a rejected transaction, an undecodable return.
+    Other(String),
+}
+
+impl Error {
+    /// The engine error a revert carries, by name (`GameStartsAndEndsSameBlock`, …).
+    pub fn engine_error(&self) -> Option<String> {
+        let Error::Reverted(data) = self else { return None };
+        let e = IEngineErrors::abi_decode(data).ok()?;
+        Some(format!("{e:?}").split('(').next().unwrap_or_default().to_string())
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Reverted(data) => match (self.engine_error(), decode_revert_reason(data)) {
+                (Some(name), _) => write!(f, "reverted: {name}"),
+                (None, Some(reason)) => write!(f, "reverted: {reason}"),
+                (None, None) => write!(f, "reverted: 0x{}", hex::encode(data)),
+            },
+            Error::Halted(reason) => write!(f, "halted: {reason}"),
+            Error::Other(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+fn rp_address(a: Address) -> rp::Address {
+    rp::Address::from_slice(a.as_slice())
+}
+
+fn rp_word(v: U256) -> rp::U256 {
+    rp::U256::from_be_bytes(v.to_be_bytes::<32>())
+}
+
+/// A JSON quantity: a number, or a decimal / 0x-hex string.
+fn quantity(v: &Value) -> Option<U256> {
+    match v {
+        Value::Number(n) => n.as_u64().map(U256::from),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+pub struct Chain {
+    evm: Evm<'static, (), CacheDB<EmptyDB>>,
+}
+
+impl Chain {
+    pub fn new(db: CacheDB<EmptyDB>, number: u64, timestamp: u64) -> Chain {
+        let evm = Evm::builder()
+            .with_db(db)
+            .with_spec_id(SpecId::CANCUN)
+            // MegaETH lifts the 24 KiB cap; the Engine is deployed over it.
+            .modify_cfg_env(|cfg| cfg.limit_contract_code_size = Some(usize::MAX))
+            .modify_block_env(|block| {
+                block.number = rp::U256::from(number);
+                block.timestamp = rp::U256::from(timestamp);
+                block.gas_limit = rp::U256::from(u64::MAX);
+                block.basefee = rp::U256::ZERO;
+            })
+            .build();
+        Chain { evm }
+    }
+
+    pub fn empty() -> Chain {
+        Chain::new(CacheDB::new(EmptyDB::default()), 1, 1)
+    }
+
+    /// Seeds the chain from anvil's `--dump-state` file (or an `anvil_dumpState` result): every
+    /// account's code, balance, nonce and storage, and the block it was dumped at.
+    pub fn from_anvil_state(text: &str) -> Result<Chain> {
+        let bad = |what: &str| Error::Other(format!("anvil state: {what}"));
+        let doc: Value = serde_json::from_str(text).map_err(|e| bad(&e.to_string()))?;
+        let accounts = doc.get("accounts").and_then(Value::as_object).ok_or_else(|| bad("no accounts"))?;
+        let mut db = CacheDB::new(EmptyDB::default());
+        for (addr, account) in accounts {
+            let address: Address = addr.parse().map_err(|_| bad(&format!("bad address {addr}")))?;
+            let code = match account.get("code").and_then(Value::as_str) {
+                Some(c) => hex::decode(c).map_err(|_| bad(&format!("bad code for {addr}")))?,
+                None => Vec::new(),
+            };
+            let info = AccountInfo {
+                balance: rp_word(account.get("balance").and_then(quantity).unwrap_or_default()),
+                nonce: account.get("nonce").and_then(quantity).map_or(0, |n| n.saturating_to()),
+                code_hash: KECCAK_EMPTY,
+                code: Some(Bytecode::new_raw(code.into())),
+            };
+            db.insert_account_info(rp_address(address), info);
+            for (slot, value) in account.get("storage").and_then(Value::as_object).into_iter().flatten() {
+                let slot: U256 = slot.parse().map_err(|_| bad(&format!("bad slot {slot} for {addr}")))?;
+                let value = quantity(value).ok_or_else(|| bad(&format!("bad value at {slot} for {addr}")))?;
+                db.insert_account_storage(rp_address(address), rp_word(slot), rp_word(value))
+                    .map_err(|_| bad("storage insert"))?;
+            }
+        }
+        let block = doc.get("block");
+        let field = |k: &str| block.and_then(|b| b.get(k)).and_then(quantity).map(|v| v.saturating_to::<u64>());
+        let number =
+            field("number").or_else(|| doc.get("best_block_number").and_then(quantity).map(|v| v.saturating_to()));
+        Ok(Chain::new(db, number.unwrap_or(0) + 1, field("timestamp").unwrap_or(0) + 1))
+    }
+
+    /// An independent copy of the chain as it stands, to run a battle on and throw away.
+    pub fn fork(&self) -> Chain {
+        let (number, timestamp) = self.block();
+        Chain::new(self.evm.db().clone(), number, timestamp)
+    }
+
+    /// `(number, timestamp)` of the block the next transaction lands in.
+    pub fn block(&self) -> (u64, u64) {
+        let b = self.evm.block();
+        (b.number.saturating_to(), b.timestamp.saturating_to())
+    }
+
+    /// Moves to the next block, `seconds` later.
+    pub fn advance(&mut self, seconds: u64) {
+        let b = self.evm.block_mut();
+        b.number += rp::U256::from(1);
+        b.timestamp += rp::U256::from(seconds);
+    }
+
+    fn run(&mut self, from: Address, to: TxKind, data: Vec<u8>, commit: bool) -> Result<Output> {
+        let tx = self.evm.tx_mut();
+        tx.caller = rp_address(from);
+        tx.transact_to = to;
+        tx.data = data.into();
+        tx.value = rp::U256::ZERO;
+        tx.gas_limit = TX_GAS;
+        tx.gas_price = rp::U256::ZERO;
+        tx.nonce = None;
+        let result = if commit { self.evm.transact_commit() } else { self.evm.transact().map(|r| r.result) };
+        match result.map_err(|e| Error::Other(format!("transaction rejected: {e:?}")))? {
+            ExecutionResult::Success { output, .. } => Ok(output),
+            ExecutionResult::Revert { output, .. } => Err(Error::Reverted(output.to_vec())),
+            ExecutionResult::Halt { reason, .. } => Err(Error::Halted(format!("{reason:?}"))),
+        }
+    }
+
+    /// Runs `init_code` (creation bytecode plus encoded constructor args) from `from`.
+    pub fn deploy(&mut self, from: Address, init_code: Vec<u8>) -> Result<Address> {
+        match self.run(from, TxKind::Create, init_code, true)? {
+            Output::Create(_, Some(at)) => Ok(Address::from_slice(at.as_slice())),
+            _ => Err(Error::Other("deployment returned no address".to_string())),
+        }
+    }
+
+    /// Sends `call` from `from` and keeps its state changes.
+    pub fn transact<C: SolCall>(&mut self, from: Address, to: Address, call: C) -> Result<C::Return> {
+        let out = self.run(from, TxKind::Call(rp_address(to)), call.abi_encode(), true)?;
+        C::abi_decode_returns(&out.into_data()).map_err(|e| Error::Other(format!("{}: {e}", C::SIGNATURE)))
+    }
+
+    /// Runs `call` against the current state and throws its changes away.
+    pub fn view<C: SolCall>(&mut self, to: Address, call: C) -> Result<C::Return> {
+        let out = self.run(Address::ZERO, TxKind::Call(rp_address(to)), call.abi_encode(), false)?;
+        C::abi_decode_returns(&out.into_data()).map_err(|e| Error::Other(format!("{}: {e}", C::SIGNATURE)))
+    }
+}
+
+/// A Foundry artifact's creation bytecode (`out/<File>.sol/<Contract>.json`, `bytecode.object`).
+pub fn artifact_bytecode(text: &str) -> Result<Vec<u8>> {
+    let doc: Value = serde_json::from_str(text).map_err(|e| Error::Other(format!("artifact: {e}")))?;
+    let object = doc
+        .get("bytecode")
+        .and_then(|b| b.get("object").or(Some(b)))
+        .and_then(Value::as_str)
+        .ok_or_else(|| Error::Other("artifact: no bytecode.object".to_string()))?;
+    if object.contains("__$") {
+        return Err(Error::Other("artifact: unlinked library placeholders in bytecode".to_string()));
+    }
+    hex::decode(object).map_err(|e| Error::Other(format!("artifact: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chomp_bindings::IEngine::getTurnIdForBattleStateCall as Get;
+
+    /// Runtime code answering any call with storage slot 0 (so any `uint256`-returning call).
+    const SLOT0_RETURNER: &str = "0x5f545f5260205ff3";
+
+    #[test]
+    fn seeds_from_a_state_dump_and_moves_blocks() {
+        let at = Address::repeat_byte(0x42);
+        let dump = format!(
+            r#"{{"block": {{"number": "0x10", "timestamp": "0x64"}},
+                "accounts": {{"{at}": {{"nonce": 1, "balance": "0x0", "code": "{SLOT0_RETURNER}",
+                                       "storage": {{"0x0": "0x2a"}}}}}}}}"#
+        );
+        let mut chain = Chain::from_anvil_state(&dump).unwrap();
+        assert_eq!(chain.block(), (17, 101));
+        assert_eq!(chain.view(at, Get { battleKey: Default::default() }).unwrap(), U256::from(42u8));
+        chain.advance(2);
+        assert_eq!(chain.block(), (18, 103));
+
+        // Init code that returns the same runtime: CODECOPY it out of the tail and RETURN it.
+        let runtime = hex::decode(SLOT0_RETURNER).unwrap();
+        let mut init = hex::decode("60088060095f395ff3").unwrap();
+        init.extend(&runtime);
+        let deployed = chain.deploy(Address::repeat_byte(1), init).unwrap();
+        assert_eq!(chain.view(deployed, Get { battleKey: Default::default() }).unwrap(), U256::ZERO);
+        assert!(matches!(Chain::from_anvil_state("{}"), Err(Error::Other(_))));
+    }
+}
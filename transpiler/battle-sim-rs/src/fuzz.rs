@@ -0,0 +1,317 @@
+//! Invariant fuzzing over the compiled engine: random teams from the mon registry play random
+//! engine-accepted actions, and every turn's state is checked — HP within `[0, max]`, stamina
+//! within `[0, base]`, the turn counter advancing, the side to act always having a legal action,
+//! and the battle ending within a turn cap. A failing case shrinks to a smaller [`Script`] that
+//! still hits the same kind of violation, which `battle-sim` replays as-is.
+
+use crate::battle::{Battle, Setup, Snapshot, MOVES_PER_MON};
+use crate::chain::{Chain, Result};
+use crate::script::{Action, MonPick, Script};
+use std::fmt;
+use std::mem::discriminant;
+
+/// splitmix64: tiny, seedable, and plenty for picking teams and actions.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n` (`n > 0`).
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    Hp {
+        side: usize,
+        mon: usize,
+        hp: i64,
+        max: u32,
+    },
+    Stamina {
+        side: usize,
+        mon: usize,
+        stamina: i64,
+        max: u32,
+    },
+    /// The engine reverted a turn made of actions it had just validated.
+    Rejected(String),
+    /// A turn executed without the turn counter moving.
+    Stalled {
+        turn: u64,
+    },
+    /// The side the engine waits on has no action `validatePlayerMoveForBattle` accepts.
+    NoValidAction {
+        side: usize,
+    },
+    /// Still no winner after this many turns.
+    TurnCap(usize),
+}
+
+impl Violation {
+    /// Whether `other` is the same kind of failure (what shrinking must preserve).
+    pub fn same_kind(&self, other: &Violation) -> bool {
+        discriminant(self) == discriminant(other)
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Hp { side, mon, hp, max } => write!(f, "p{side} mon {mon} at {hp} HP (max {max})"),
+            Violation::Stamina { side, mon, stamina, max } => {
+                write!(f, "p{side} mon {mon} at {stamina} stamina (base {max})")
+            }
+            Violation::Rejected(e) => write!(f, "validated actions rejected: {e}"),
+            Violation::Stalled { turn } => write!(f, "turn counter stuck at {turn}"),
+            Violation::NoValidAction { side } => write!(f, "p{side} has no valid action"),
+            Violation::TurnCap(n) => write!(f, "no winner after {n} turns"),
+        }
+    }
+}
+
+/// The per-state invariants: every mon's HP and stamina within bounds.
+pub fn check(state: &Snapshot) -> Option<Violation> {
+    for (side, s) in state.sides.iter().enumerate() {
+        for (mon, m) in s.mons.iter().enumerate() {
+            if m.hp < 0 || m.hp > m.max_hp as i64 {
+                return Some(Violation::Hp { side, mon, hp: m.hp, max: m.max_hp });
+            }
+            if m.stamina < 0 || m.stamina > m.max_stamina as i64 {
+                return Some(Violation::Stamina { side, mon, stamina: m.stamina, max: m.max_stamina });
+            }
+        }
+    }
+    None
+}
+
+/// Every action `side` could submit, filtered through `validatePlayerMoveForBattle`.
+pub fn valid_actions(battle: &mut Battle, side: usize) -> Result<Vec<Action>> {
+    let mut candidates: Vec<Action> =
+        (0..MOVES_PER_MON as u8).map(|lane| Action::Move { lane, extra_data: 0 }).collect();
+    candidates.extend((0..battle.team_size(side) as u8).map(|mon| Action::Switch { mon }));
+    candidates.push(Action::NoOp);
+    let mut valid = Vec::new();
+    for action in candidates {
+        if battle.is_valid(side, action)? {
+            valid.push(action);
+        }
+    }
+    Ok(valid)
+}
+
+/// A failing case: the script up to and including the turn that broke an invariant.
+#[derive(Clone, Debug)]
+pub struct Failure {
+    pub script: Script,
+    pub violation: Violation,
+}
+
+/// Plays turn `i` of `script` and checks the state it leaves.
+fn play_turn(battle: &mut Battle, script: &Script, i: usize) -> Result<Option<Violation>> {
+    let before = battle.snapshot()?.turn;
+    if let Err(e) = battle.step(script.turns[i], [script.salt(i, 0), script.salt(i, 1)]) {
+        return Ok(Some(Violation::Rejected(e.to_string())));
+    }
+    let state = battle.snapshot()?;
+    Ok(check(&state).or((state.turn == before).then_some(Violation::Stalled { turn: before })))
+}
+
+/// One random case: distinct mons per side drawn from `mon_ids`, then actions drawn from what the
+/// engine accepts each turn, until the battle ends, an invariant breaks, or `max_turns` pass.
+pub fn explore(
+    base: &Chain,
+    setup: &Setup,
+    rng: &mut Rng,
+    mon_ids: &[u64],
+    team_size: usize,
+    max_turns: usize,
+) -> Result<Option<Failure>> {
+    let mut draw = || {
+        let mut pool = mon_ids.to_vec();
+        (0..team_size.min(pool.len())).map(|_| MonPick::Id(pool.swap_remove(rng.below(pool.len())))).collect()
+    };
+    let (p0, p1) = (draw(), draw());
+    let mut script = Script { seed: rng.next_u64(), p0, p1, turns: Vec::new(), note: None };
+    let mut battle = Battle::start(base.fork(), setup, [&script.p0, &script.p1])?;
+    let fail = |script: Script, violation| Ok(Some(Failure { script, violation }));
+    if let Some(v) = check(&battle.snapshot()?) {
+        return fail(script, v);
+    }
+    while script.turns.len() < max_turns {
+        let to_move = battle.to_move()?;
+        if to_move == [false, false] {
+            return Ok(None);
+        }
+        let mut turn = [Action::NoOp; 2];
+        for side in (0..2).filter(|&s| to_move[s]) {
+            let valid = valid_actions(&mut battle, side)?;
+            if valid.is_empty() {
+                return fail(script, Violation::NoValidAction { side });
+            }
+            turn[side] = valid[rng.below(valid.len())];
+        }
+        script.turns.push(turn);
+        if let Some(v) = play_turn(&mut battle, &script, script.turns.len() - 1)? {
+            return fail(script, v);
+        }
+    }
+    if battle.winner()?.is_none() {
+        return fail(script, Violation::TurnCap(max_turns));
+    }
+    Ok(None)
+}
+
+/// Replays `script` on a fork of `base` and returns the first violation it hits. Actions the
+/// engine would not accept mean the script no longer describes a legal battle, so it hits
+/// nothing; a script that runs out of turns is checked for a side left with no legal action.
+pub fn replay(base: &Chain, setup: &Setup, script: &Script, max_turns: usize) -> Result<Option<Violation>> {
+    let mut battle = Battle::start(base.fork(), setup, [&script.p0, &script.p1])?;
+    if let Some(v) = check(&battle.snapshot()?) {
+        return Ok(Some(v));
+    }
+    for i in 0..script.turns.len() {
+        let to_move = battle.to_move()?;
+        if to_move == [false, false] {
+            return Ok(None);
+        }
+        for side in (0..2).filter(|&s| to_move[s]) {
+            if !battle.is_valid(side, script.turns[i][side])? {
+                return Ok(None);
+            }
+        }
+        if let Some(v) = play_turn(&mut battle, script, i)? {
+            return Ok(Some(v));
+        }
+    }
+    let to_move = battle.to_move()?;
+    for side in (0..2).filter(|&s| to_move[s]) {
+        if valid_actions(&mut battle, side)?.is_empty() {
+            return Ok(Some(Violation::NoValidAction { side }));
+        }
+    }
+    if script.turns.len() >= max_turns && battle.winner()?.is_none() {
+        return Ok(Some(Violation::TurnCap(max_turns)));
+    }
+    Ok(None)
+}
+
+/// Greedily shrinks a failing script while `fails` still holds: drops runs of turns (halving the
+/// run length down to one), then single team members, then rewrites actions to `noop` / lane 0,
+/// repeating until a full pass changes nothing.
+pub fn shrink(script: &Script, mut fails: impl FnMut(&Script) -> bool) -> Script {
+    let mut best = script.clone();
+    loop {
+        let mut progress = false;
+
+        let mut run = (best.turns.len() / 2).max(1);
+        while run > 0 && !best.turns.is_empty() {
+            let mut i = 0;
+            while i < best.turns.len() {
+                let mut candidate = best.clone();
+                candidate.turns.drain(i..(i + run).min(candidate.turns.len()));
+                if fails(&candidate) {
+                    best = candidate;
+                    progress = true;
+                } else {
+                    i += run;
+                }
+            }
+            run /= 2;
+        }
+
+        for side in 0..2 {
+            let mut j = 0;
+            while j < best.team(side).len() {
+                let mut candidate = best.clone();
+                if candidate.team(side).len() > 1 {
+                    candidate.team_mut(side).remove(j);
+                    if fails(&candidate) {
+                        best = candidate;
+                        progress = true;
+                        continue;
+                    }
+                }
+                j += 1;
+            }
+        }
+
+        for i in 0..best.turns.len() {
+            for side in 0..2 {
+                let simpler = match best.turns[i][side] {
+                    Action::NoOp => continue,
+                    Action::Move { lane: 0, extra_data: 0 } => Action::NoOp,
+                    Action::Move { .. } => Action::Move { lane: 0, extra_data: 0 },
+                    Action::Switch { .. } => Action::NoOp,
+                };
+                let mut candidate = best.clone();
+                candidate.turns[i][side] = simpler;
+                if fails(&candidate) {
+                    best = candidate;
+                    progress = true;
+                }
+            }
+        }
+
+        if !progress {
+            return best;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mv(lane: u8) -> Action {
+        Action::Move { lane, extra_data: 0 }
+    }
+
+    #[test]
+    fn shrinks_to_the_turns_and_mons_that_matter() {
+        let script = Script {
+            seed: 9,
+            p0: [3, 1, 4, 1].map(MonPick::Id).to_vec(),
+            p1: [5, 9, 2, 6].map(MonPick::Id).to_vec(),
+            turns: vec![
+                [Action::Switch { mon: 0 }, Action::Switch { mon: 2 }],
+                [mv(2), mv(1)],
+                [mv(3), Action::Switch { mon: 1 }],
+                [mv(1), mv(3)],
+                [Action::Switch { mon: 2 }, mv(0)],
+                [mv(3), mv(2)],
+            ],
+            note: None,
+        };
+        // "Fails" whenever p0 plays lane 3 while p1 fields mon 9 — wherever the turn sits.
+        let fails = |s: &Script| s.p1.iter().any(|m| m.id() == 9) && s.turns.iter().any(|t| t[0] == mv(3));
+        assert!(fails(&script));
+        let small = shrink(&script, fails);
+        assert!(fails(&small));
+        assert_eq!(small.turns, vec![[mv(3), Action::NoOp]]);
+        assert_eq!(small.p0.len(), 1);
+        assert_eq!(small.p1, vec![MonPick::Id(9)]);
+        assert_eq!(small.seed, 9);
+    }
+
+    #[test]
+    fn rng_is_seeded_and_in_range() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        let draws: Vec<usize> = (0..100).map(|_| a.below(6)).collect();
+        assert_eq!(draws, (0..100).map(|_| b.below(6)).collect::<Vec<_>>());
+        assert!(draws.iter().all(|&d| d < 6));
+        assert!((0..6).all(|d| draws.contains(&d)));
+    }
+}
@@ -0,0 +1,18 @@
+//! The compiled engine under revm: [`chain`] is an in-memory EVM seeded from an anvil state dump
+//! of a local deployment, [`script`] is the battle script format, [`battle`] fields scripted
+//! teams and plays them turn by turn against the deployed Engine bytecode, and [`fuzz`] plays
+//! random battles against the engine's invariants and shrinks the ones that break them.
+
+pub mod battle;
+pub mod chain;
+pub mod fuzz;
+pub mod script;
+
+use std::path::PathBuf;
+
+/// The chomp checkout: `CHOMP_ROOT`, else two levels above this crate (which builds in place).
+pub fn chomp_root() -> PathBuf {
+    std::env::var("CHOMP_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join(".."))
+}
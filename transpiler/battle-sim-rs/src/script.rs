@@ -0,0 +1,172 @@
+//! Battle scripts: the two teams (registry mon ids, optionally with a move loadout) and the turns
+//! to play, as JSON. The fuzzer writes its failing cases in the same shape, so `battle-sim`
+//! replays them as-is.
+//!
+//! ```json
+//! { "seed": 7,
+//!   "p0": [0, 3, {"id": 7, "moves": [0, 1, 2, 5]}, 1],
+//!   "p1": [2, 4, 5, 6],
+//!   "turns": [["switch 0", "switch 2"], ["move 1", "move 0 @2"], ["switch 3", "noop"]] }
+//! ```
+//!
+//! Actions are `move <lane> [@<extraData>]`, `switch <team slot>` or `noop`. A turn where the
+//! engine wants only one side to act (a forced switch after a KO) plays that side's action and
+//! ignores the other.
+
+use alloy_primitives::keccak256;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// `Constants.sol`'s `SWITCH_MOVE_INDEX` / `NO_OP_MOVE_INDEX`.
+pub const SWITCH_MOVE_INDEX: u8 = 125;
+pub const NO_OP_MOVE_INDEX: u8 = 126;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Action {
+    Move { lane: u8, extra_data: u16 },
+    Switch { mon: u8 },
+    NoOp,
+}
+
+impl Action {
+    /// `(moveIndex, extraData)` as the engine's `executeWith*` entrypoints take them.
+    pub fn encode(self) -> (u8, u16) {
+        match self {
+            Action::Move { lane, extra_data } => (lane, extra_data),
+            Action::Switch { mon } => (SWITCH_MOVE_INDEX, mon as u16),
+            Action::NoOp => (NO_OP_MOVE_INDEX, 0),
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Move { lane, extra_data: 0 } => write!(f, "move {lane}"),
+            Action::Move { lane, extra_data } => write!(f, "move {lane} @{extra_data}"),
+            Action::Switch { mon } => write!(f, "switch {mon}"),
+            Action::NoOp => f.write_str("noop"),
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Action, String> {
+        let bad = || format!("bad action {s:?} (want `move <lane> [@<extra>]`, `switch <mon>` or `noop`)");
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            ["noop"] => Ok(Action::NoOp),
+            ["switch", mon] => Ok(Action::Switch { mon: mon.parse().map_err(|_| bad())? }),
+            ["move", lane, rest @ ..] => {
+                let lane = lane.parse().map_err(|_| bad())?;
+                let extra_data = match rest {
+                    [] => 0,
+                    [extra] => extra.strip_prefix('@').and_then(|e| e.parse().ok()).ok_or_else(bad)?,
+                    _ => return Err(bad()),
+                };
+                Ok(Action::Move { lane, extra_data })
+            }
+            _ => Err(bad()),
+        }
+    }
+}
+
+impl TryFrom<String> for Action {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Action, String> {
+        s.parse()
+    }
+}
+
+impl From<Action> for String {
+    fn from(a: Action) -> String {
+        a.to_string()
+    }
+}
+
+/// A team member: a bare registry id takes the mon's default loadout (its first four moves);
+/// `moves` picks lanes of its full move row instead, in order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MonPick {
+    Id(u64),
+    Loadout { id: u64, moves: Vec<usize> },
+}
+
+impl MonPick {
+    pub fn id(&self) -> u64 {
+        match self {
+            MonPick::Id(id) | MonPick::Loadout { id, .. } => *id,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Script {
+    /// Salts (and so the engine's RNG) derive from this; same seed, same battle.
+    #[serde(default)]
+    pub seed: u64,
+    pub p0: Vec<MonPick>,
+    pub p1: Vec<MonPick>,
+    pub turns: Vec<[Action; 2]>,
+    /// Free text riding along with the script; the fuzzer records the violation a case hits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl Script {
+    pub fn parse(text: &str) -> Result<Script, String> {
+        serde_json::from_str(text).map_err(|e| e.to_string())
+    }
+
+    pub fn team(&self, side: usize) -> &Vec<MonPick> {
+        if side == 0 {
+            &self.p0
+        } else {
+            &self.p1
+        }
+    }
+
+    pub fn team_mut(&mut self, side: usize) -> &mut Vec<MonPick> {
+        if side == 0 {
+            &mut self.p0
+        } else {
+            &mut self.p1
+        }
+    }
+
+    /// The 104-bit salt `side` commits on script turn `turn`.
+    pub fn salt(&self, turn: usize, side: usize) -> u128 {
+        let mut buf = [0u8; 24];
+        buf[..8].copy_from_slice(&self.seed.to_be_bytes());
+        buf[8..16].copy_from_slice(&(turn as u64).to_be_bytes());
+        buf[16..].copy_from_slice(&(side as u64).to_be_bytes());
+        u128::from_be_bytes(keccak256(buf)[..16].try_into().unwrap()) >> 24
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_round_trip_through_json() {
+        let text = r#"{"seed": 7, "p0": [0, {"id": 7, "moves": [0, 1, 2, 5]}], "p1": [2],
+                       "turns": [["switch 0", "switch 1"], ["move 1", "move 0 @2"], ["noop", "move 3"]]}"#;
+        let script = Script::parse(text).unwrap();
+        assert_eq!(script.p0[1], MonPick::Loadout { id: 7, moves: vec![0, 1, 2, 5] });
+        assert_eq!(script.turns[1], [Action::Move { lane: 1, extra_data: 0 }, Action::Move { lane: 0, extra_data: 2 }]);
+        assert_eq!(script.turns[0][1].encode(), (SWITCH_MOVE_INDEX, 1));
+        assert_eq!(Action::NoOp.encode(), (NO_OP_MOVE_INDEX, 0));
+        assert_eq!(Script::parse(&serde_json::to_string(&script).unwrap()).unwrap(), script);
+
+        assert!(script.salt(0, 0) < 1 << 104);
+        assert_ne!(script.salt(0, 0), script.salt(0, 1));
+        assert!(Script::parse(r#"{"p0": [], "p1": [], "turns": [["jump", "noop"]]}"#).is_err());
+    }
+}
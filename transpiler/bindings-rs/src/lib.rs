@@ -0,0 +1,612 @@
+//! ABI bindings for the deployed chomp contracts, written once with alloy's `sol!` so every Rust
+//! tool encodes calls, decodes returns / reverts and matches event logs the same way.
+//!
+//! Declarations are hand-copied from `src/` (structs from `Structs.sol`, the interface surface from
+//! `IEngine.sol`, `ITeamRegistry.sol`, the matchmakers and commit managers) and, for the revm
+//! simulators, `test/mocks/TestTeamRegistry.sol`; contract-typed fields (`ITeamRegistry`,
+//! `IEffect`, …) are spelled `address`, which is what the ABI sees. Only the surface the tools use
+//! is declared — extend a block when a tool needs more, keeping the Solidity spelling so selectors
+//! and topics stay exact.
+//!
+//! [`eip712`] carries the signed-message structs (dual-signed reveals, battle offers, seat fills)
+//! under their on-chain type names, plus the domains each verifying contract uses.
+
+#![allow(clippy::too_many_arguments)]
+
+pub use alloy_primitives;
+pub use alloy_sol_types;
+
+use alloy_sol_types::sol;
+
+sol! {
+    #![sol(all_derives)]
+
+    enum Type { Yin, Yang, Earth, Liquid, Fire, Metal, Ice, Nature, Lightning, Faith, Air, Math, Cyber, Cosmic, None }
+
+    enum MonStateIndexName {
+        Hp, Stamina, Speed, Attack, Defense, SpecialAttack, SpecialDefense, IsKnockedOut, ShouldSkipTurn, Type1, Type2
+    }
+
+    struct ProposedBattle {
+        address p0;
+        uint96 p0TeamIndex;
+        bytes32 p0TeamHash;
+        address p1;
+        uint96 p1TeamIndex;
+        address teamRegistry;
+        address rngOracle;
+        address ruleset;
+        address moveManager;
+        address matchmaker;
+        address[] engineHooks;
+    }
+
+    struct Battle {
+        address p0;
+        uint96 p0TeamIndex;
+        address p1;
+        uint96 p1TeamIndex;
+        address p2;
+        uint96 p2TeamIndex;
+        address p3;
+        uint96 p3TeamIndex;
+        address teamRegistry;
+        address rngOracle;
+        address ruleset;
+        address moveManager;
+        address matchmaker;
+        address[] engineHooks;
+    }
+
+    struct BattleOffer {
+        Battle battle;
+        uint256 pairHashNonce;
+        uint8 battleMode;
+    }
+
+    struct SeatPhantomConfig {
+        uint256[] monIndices;
+        uint8[] facetIds;
+        uint8[] moveSelections;
+    }
+
+    struct MoveDecision {
+        uint8 packedMoveIndex;
+        uint16 extraData;
+    }
+
+    struct EffectInstance {
+        address effect;
+        uint16 stepsBitmap;
+        bytes32 data;
+    }
+
+    struct GlobalKVEntry {
+        uint64 key;
+        bytes32 value;
+    }
+
+    struct MonStats {
+        uint32 hp;
+        uint32 stamina;
+        uint32 speed;
+        uint32 attack;
+        uint32 defense;
+        uint32 specialAttack;
+        uint32 specialDefense;
+        Type type1;
+        Type type2;
+    }
+
+    struct Mon {
+        MonStats stats;
+        uint256 ability;
+        uint256[] moves;
+    }
+
+    struct MonState {
+        int32 hpDelta;
+        int32 staminaDelta;
+        int32 speedDelta;
+        int32 attackDelta;
+        int32 defenceDelta;
+        int32 specialAttackDelta;
+        int32 specialDefenceDelta;
+        bool isKnockedOut;
+        bool shouldSkipTurn;
+    }
+
+    struct TeamLevelInfo {
+        uint256[] monIds;
+        uint256[] exp;
+        uint256[] levels;
+    }
+
+    struct BattleConfigView {
+        address rngOracle;
+        address moveManager;
+        uint24 globalEffectsLength;
+        uint96 packedP0EffectsCount;
+        uint96 packedP1EffectsCount;
+        uint8 teamSizes;
+        uint40 startTimestamp;
+        uint104 p0Salt;
+        uint104 p1Salt;
+        uint16 p0TeamIndex;
+        uint16 p1TeamIndex;
+        MoveDecision p0Move;
+        MoveDecision p1Move;
+        EffectInstance[] globalEffects;
+        EffectInstance[][] p0Effects;
+        EffectInstance[][] p1Effects;
+        Mon[][] teams;
+        MonState[][] monStates;
+        GlobalKVEntry[] globalKVEntries;
+        TeamLevelInfo p0Levels;
+        TeamLevelInfo p1Levels;
+    }
+
+    struct BattleData {
+        address p1;
+        uint16 p0TeamIndex;
+        uint16 p1TeamIndex;
+        bool usesBuiltinManager;
+        bool isTwoSlotMode;
+        bool isMultiMode;
+        uint16 activeMonExt;
+        address p0;
+        uint8 winnerIndex;
+        uint8 playerSwitchForTurnFlag;
+        uint16 activeMonIndex;
+        uint40 lastExecuteTimestamp;
+        uint16 turnId;
+        uint8 numBuffered;
+    }
+
+    interface IEngine {
+        event BattleStart(bytes32 indexed battleKey, address p0, address p1);
+        event SlotBattleStart(bytes32 indexed battleKey, address p0, address p1, uint8 battleMode, address p2, address p3);
+        event MonMoves(bytes32 indexed battleKey, uint256 packedMoves, uint256 packedSalts);
+        event EngineExecute(bytes32 indexed battleKey);
+        event BattleComplete(bytes32 indexed battleKey, address winner);
+        event BattleCompleteWithBatchTurns(bytes32 indexed battleKey, bytes payload);
+        event BattleCompleteWithBatchSlotTurns(bytes32 indexed battleKey, bytes payload);
+        event MovesSubmitted(bytes32 indexed battleKey, bytes32 packed);
+        event SlotMovesSubmitted(bytes32 indexed battleKey, uint256 side0Packed, uint256 side1Packed);
+
+        error NoWriteAllowed();
+        error WrongCaller();
+        error MatchmakerNotAuthorized();
+        error MovesNotSet();
+        error InvalidBattleConfig();
+        error GameAlreadyOver();
+        error GameStartsAndEndsSameBlock();
+        error NotPlayerInBattle();
+        error BattleNotStarted();
+        error NotTwoPlayerTurn();
+        error NotSinglePlayerTurn();
+        error NotBuiltInManager();
+        error SideWordOverflow();
+        error WrongBattleMode();
+        error NotCommitter();
+        error InvalidSignature();
+        error EmptyBuffer();
+
+        function updateMatchmakers(address[] memory makersToAdd, address[] memory makersToRemove) external;
+        function startBattle(Battle memory battle) external;
+        function startBattleWithMode(Battle memory battle, uint8 battleMode) external;
+        function execute(bytes32 battleKey) external returns (address winner);
+        function executeWithMoves(
+            bytes32 battleKey,
+            uint8 p0MoveIndex,
+            uint104 p0Salt,
+            uint16 p0ExtraData,
+            uint8 p1MoveIndex,
+            uint104 p1Salt,
+            uint16 p1ExtraData
+        ) external returns (address winner);
+        function executeWithSingleMove(bytes32 battleKey, uint8 moveIndex, uint104 salt, uint16 extraData)
+            external
+            returns (address winner);
+        function executeWithSlotMoves(bytes32 battleKey, uint256 side0Packed, uint256 side1Packed)
+            external
+            returns (address winner);
+        function executeBatchedTurns(bytes32 battleKey, uint256[] calldata entries)
+            external
+            returns (uint64 executed, address winner);
+        function executeBatchedSlotTurns(bytes32 battleKey, uint256[] calldata entries)
+            external
+            returns (uint64 executed, address winner);
+        function submitTurnMoves(bytes32 battleKey, uint256 packedMoves, bytes32 r, bytes32 vs) external;
+        function submitTurnMovesAndExecute(bytes32 battleKey, uint256 packedMoves, bytes32 r, bytes32 vs) external;
+        function submitSlotTurnMoves(
+            bytes32 battleKey,
+            uint256 committerSidePacked,
+            uint256 revealerSidePacked,
+            bytes32 r,
+            bytes32 vs
+        ) external;
+        function submitSlotTurnMovesAndExecute(
+            bytes32 battleKey,
+            uint256 committerSidePacked,
+            uint256 revealerSidePacked,
+            bytes32 r,
+            bytes32 vs
+        ) external;
+        function executeBuffered(bytes32 battleKey) external;
+        function getBufferedTurns(bytes32 battleKey) external view returns (uint64 numExecuted, uint256[] memory packedTurns);
+        function end(bytes32 battleKey) external;
+        function forfeit(bytes32 battleKey) external;
+
+        function pairHashNonces(bytes32 pairHash) external view returns (uint256);
+        function computeBattleKey(address p0, address p1) external view returns (bytes32 battleKey, bytes32 pairHash);
+        function computePartyKey(address p0, address p1, address p2, address p3)
+            external
+            view
+            returns (bytes32 battleKey, bytes32 partyHash);
+        function getSeats(bytes32 battleKey) external view returns (address[4] memory seats);
+        function getStorageKey(bytes32 battleKey) external view returns (bytes32);
+        function getBattle(bytes32 battleKey) external view returns (BattleConfigView memory config, BattleData memory data);
+        function getMonStatsForBattle(bytes32 battleKey, uint256 playerIndex, uint256 monIndex)
+            external
+            view
+            returns (MonStats memory);
+        function getMonStateForBattle(
+            bytes32 battleKey,
+            uint256 playerIndex,
+            uint256 monIndex,
+            MonStateIndexName stateVarIndex
+        ) external view returns (int32);
+        function getMonStatesForSide(bytes32 battleKey, uint256 playerIndex) external view returns (MonState[] memory);
+        function getMoveForMonForBattle(bytes32 battleKey, uint256 playerIndex, uint256 monIndex, uint256 moveIndex)
+            external
+            view
+            returns (uint256);
+        function getTeamSize(bytes32 battleKey, uint256 playerIndex) external view returns (uint256);
+        function getTurnIdForBattleState(bytes32 battleKey) external view returns (uint256);
+        function getActiveMonIndexForBattleState(bytes32 battleKey) external view returns (uint256[] memory);
+        function getEffects(bytes32 battleKey, uint256 targetIndex, uint256 monIndex)
+            external
+            view
+            returns (EffectInstance[] memory effects, uint256[] memory indices);
+        function getWinner(bytes32 battleKey) external view returns (address);
+        function getKOBitmap(bytes32 battleKey, uint256 playerIndex) external view returns (uint256);
+        function validatePlayerMoveForBattle(bytes32 battleKey, uint256 moveIndex, uint256 playerIndex, uint16 extraData)
+            external
+            returns (bool);
+    }
+
+    interface IEffect {
+        function name() external returns (string memory);
+        function getStepsBitmap() external view returns (uint16);
+    }
+
+    interface IMoveSet {
+        function name() external view returns (string memory);
+    }
+
+    /// `ITeamRegistry` plus the `GachaTeamRegistry` events the exporters scan.
+    interface ITeamRegistry {
+        event Roll(address indexed player, uint256[] monIds, uint256 pointsSpent);
+
+        function createTeam(uint256[] memory monIndices) external returns (uint256 slot);
+        function getTeam(address player, uint256 teamIndex) external returns (Mon[] memory);
+        function getTeams(address p0, uint256 p0TeamIndex, address p1, uint256 p1TeamIndex)
+            external
+            returns (Mon[] memory, Mon[] memory);
+        function getTeamCount(address player) external returns (uint256);
+        function getMonRegistryIndicesForTeam(address player, uint256 teamIndex) external returns (uint256[] memory);
+        function getOrderedLiveTeams(address player) external view returns (uint256[] memory slots);
+        function getPlayerTeams(address player)
+            external
+            view
+            returns (uint256[] memory slots, uint256[][] memory teamMonIds);
+        function isWhitelistedOpponent(address addr) external view returns (bool);
+        function getMonData(uint256 monId)
+            external
+            view
+            returns (MonStats memory mon, uint256[] memory moves, uint256[] memory abilities);
+        function getMonCount() external view returns (uint256);
+        function getMonIds(uint256 start, uint256 end) external view returns (uint256[] memory);
+        function getMoveSelection(address player, uint256 monId) external view returns (uint8 bitmap);
+        function getMovePool(uint256 monId) external view returns (uint256[] memory moves, uint8[] memory unlockLevels);
+    }
+
+    /// `test/mocks/TestTeamRegistry.sol`: teams set directly, no ownership checks. The local
+    /// simulators deploy it so a scripted battle can field any mons.
+    interface TestTeamRegistry {
+        function setTeam(address player, Mon[] memory team) external;
+    }
+
+    /// Production matchmaking: one EIP-712 `BattleOffer`, consented to per seat.
+    interface SignedMatchmaker {
+        error InvalidSignature();
+        error InvalidNonce();
+        error InvalidOpenBattleOfferNonce();
+        error MissingConsent();
+        error CreatorSeatCannotBeOpen();
+        error InvalidOpenSeatsMask();
+
+        function startGame(BattleOffer memory offer, uint8 openSeatsMask, bytes[4] calldata seatSigs) external;
+        function startGameWithSeatConfigs(
+            BattleOffer memory offer,
+            uint8 openSeatsMask,
+            bytes[4] calldata seatSigs,
+            SeatPhantomConfig[3] calldata seatConfigs
+        ) external;
+        function hashTypedData(bytes32 structHash) external view returns (bytes32);
+        function openBattleOfferNonce(address creator) external view returns (uint256);
+    }
+
+    /// Legacy (deprecated, test-suite-only) propose / accept / confirm matchmaker.
+    interface DefaultMatchmaker {
+        event BattleProposal(
+            bytes32 indexed battleKey, address indexed p0, address indexed p1, bool isFastBattle, bytes32 p0TeamHash
+        );
+        event BattleAcceptance(bytes32 indexed battleKey, address indexed p1, bytes32 indexed updatedBattleKey);
+
+        error P0P1Same();
+        error ProposerNotP0();
+        error AcceptorNotP1();
+        error ConfirmerNotP0();
+        error AlreadyAccepted();
+        error BattleChangedBeforeAcceptance();
+        error InvalidP0TeamHash();
+        error BattleNotAccepted();
+
+        function proposeBattle(ProposedBattle memory proposal) external returns (bytes32 battleKey);
+        function acceptBattle(bytes32 battleKey, uint96 p1TeamIndex, bytes32 battleIntegrityHash)
+            external
+            returns (bytes32 updatedBattleKey);
+        function confirmBattle(bytes32 battleKey, bytes32 salt, uint96 p0TeamIndex) external;
+        function getBattleProposalIntegrityHash(ProposedBattle memory proposal) external pure returns (bytes32);
+    }
+
+    /// Legacy (deprecated) two-transaction commit / reveal manager.
+    interface DefaultCommitManager {
+        event MoveCommit(bytes32 indexed battleKey, address player);
+        event MoveReveal(bytes32 indexed battleKey, address player, uint256 moveIndex);
+
+        function commitMove(bytes32 battleKey, bytes32 moveHash) external;
+        function revealMove(bytes32 battleKey, uint8 moveIndex, uint104 salt, uint16 extraData, bool autoExecute) external;
+        function getCommitment(bytes32 battleKey, address player) external view returns (bytes32 moveHash, uint256 turnId);
+    }
+
+    /// External dual-signed manager (singles, one transaction per turn).
+    interface SignedCommitManager {
+        function executeWithDualSignedMoves(
+            bytes32 battleKey,
+            uint8 committerMoveIndex,
+            uint104 committerSalt,
+            uint16 committerExtraData,
+            uint8 revealerMoveIndex,
+            uint104 revealerSalt,
+            uint16 revealerExtraData,
+            bytes calldata revealerSignature
+        ) external;
+        function executeSinglePlayerMove(bytes32 battleKey, uint8 moveIndex, uint104 salt, uint16 extraData) external;
+        function commitWithSignature(bytes32 battleKey, bytes32 moveHash, bytes calldata committerSignature) external;
+    }
+
+    /// The CreateX factory entrypoints the deploy paths use (CREATE2 / CREATE3 with a guarded salt).
+    interface ICreateX {
+        event ContractCreation(address indexed newContract, bytes32 indexed salt);
+        event Create3ProxyContractCreation(address indexed newContract, bytes32 indexed salt);
+
+        function deployCreate2(bytes32 salt, bytes memory initCode) external payable returns (address newContract);
+        function deployCreate3(bytes32 salt, bytes memory initCode) external payable returns (address newContract);
+        function computeCreate2Address(bytes32 salt, bytes32 initCodeHash) external view returns (address computedAddress);
+        function computeCreate3Address(bytes32 salt) external view returns (address computedAddress);
+    }
+}
+
+/// CreateX's canonical deployment, identical on every chain it is deployed to.
+pub const CREATEX: alloy_primitives::Address = alloy_primitives::address!("ba5Ed099633D3B313e4D5F7bdc1305d3c28ba5Ed");
+
+/// EIP-712 signed messages. These live apart from the ABI structs above because the signing form
+/// of a `BattleOffer` carries an `openSeatsMask` field the calldata struct does not; the Solidity
+/// names are kept so the type hashes match the contracts'.
+pub mod eip712 {
+    use alloy_primitives::{Address, FixedBytes};
+    use alloy_sol_types::{sol, Eip712Domain, SolStruct};
+
+    sol! {
+        #![sol(all_derives)]
+
+        struct DualSignedReveal {
+            bytes32 battleKey;
+            uint64 turnId;
+            bytes32 committerMoveHash;
+            uint8 revealerMoveIndex;
+            uint104 revealerSalt;
+            uint16 revealerExtraData;
+        }
+
+        struct DualSignedSlotReveal {
+            bytes32 battleKey;
+            uint64 turnId;
+            bytes32 committerMovesHash;
+            uint256 revealerSidePacked;
+        }
+
+        struct SignedCommit {
+            bytes32 moveHash;
+            bytes32 battleKey;
+            uint64 turnId;
+        }
+
+        struct Battle {
+            address p0;
+            uint96 p0TeamIndex;
+            address p1;
+            uint96 p1TeamIndex;
+            address p2;
+            uint96 p2TeamIndex;
+            address p3;
+            uint96 p3TeamIndex;
+            address teamRegistry;
+            address rngOracle;
+            address ruleset;
+            address moveManager;
+            address matchmaker;
+            address[] engineHooks;
+        }
+
+        struct BattleOffer {
+            Battle battle;
+            uint256 pairHashNonce;
+            uint8 battleMode;
+            uint8 openSeatsMask;
+        }
+
+        struct SeatFill {
+            bytes32 offerDigest;
+            uint8 seatIndex;
+        }
+    }
+
+    fn domain(name: &'static str, chain_id: u64, verifying_contract: Address) -> Eip712Domain {
+        Eip712Domain::new(
+            Some(name.into()),
+            Some("1".into()),
+            Some(chain_id.try_into().unwrap()),
+            Some(verifying_contract),
+            None,
+        )
+    }
+
+    /// The built-in dual-signed buffer verifies against the Engine itself.
+    pub fn engine_domain(chain_id: u64, engine: Address) -> Eip712Domain {
+        domain("ChompEngine", chain_id, engine)
+    }
+
+    pub fn signed_matchmaker_domain(chain_id: u64, matchmaker: Address) -> Eip712Domain {
+        domain("SignedMatchmaker", chain_id, matchmaker)
+    }
+
+    pub fn signed_commit_manager_domain(chain_id: u64, manager: Address) -> Eip712Domain {
+        domain("SignedCommitManager", chain_id, manager)
+    }
+
+    /// The signing form `BattleOfferLib.hashBattleOfferForSigning` hashes: every team index
+    /// blinded to 0 and each seat marked open in `open_seats_mask` (canonical order
+    /// [p0, p2, p1, p3]) zeroed.
+    pub fn offer_signing_form(offer: &super::BattleOffer, open_seats_mask: u8) -> BattleOffer {
+        let b = &offer.battle;
+        let seat = |bit: u8, a: Address| if open_seats_mask & bit != 0 { Address::ZERO } else { a };
+        BattleOffer {
+            battle: Battle {
+                p0: seat(1, b.p0),
+                p1: seat(4, b.p1),
+                p2: seat(2, b.p2),
+                p3: seat(8, b.p3),
+                teamRegistry: b.teamRegistry,
+                rngOracle: b.rngOracle,
+                ruleset: b.ruleset,
+                moveManager: b.moveManager,
+                matchmaker: b.matchmaker,
+                engineHooks: b.engineHooks.clone(),
+                ..Default::default()
+            },
+            pairHashNonce: offer.pairHashNonce,
+            battleMode: offer.battleMode,
+            openSeatsMask: open_seats_mask,
+        }
+    }
+
+    /// The digest a seat signs for `startGame`: the offer digest itself for a named seat, a
+    /// `SeatFill` over it for a seat marked open.
+    pub fn seat_digest(
+        domain: &Eip712Domain,
+        offer: &super::BattleOffer,
+        open_seats_mask: u8,
+        canonical_seat: u8,
+    ) -> FixedBytes<32> {
+        let open = offer_signing_form(offer, open_seats_mask).eip712_signing_hash(domain);
+        if open_seats_mask & (1 << canonical_seat) != 0 {
+            SeatFill { offerDigest: open, seatIndex: canonical_seat }.eip712_signing_hash(domain)
+        } else {
+            open
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::eip712::{self, offer_signing_form, seat_digest, signed_matchmaker_domain};
+    use super::*;
+    use alloy_primitives::{b256, keccak256, Address, U256};
+    use alloy_sol_types::{SolCall, SolEvent, SolStruct};
+
+    // Type hashes are pinned as constants in the contracts; a drifted field name or order here
+    // would silently produce signatures the chain rejects.
+    #[test]
+    fn eip712_type_hashes_match_the_contracts() {
+        assert_eq!(
+            eip712::Battle::default().eip712_type_hash(),
+            b256!("df0031fd9cbf0d756cd8f6b99864409022693748ce3c5f2b65124630ebcdf8a2")
+        );
+        assert_eq!(
+            eip712::BattleOffer::default().eip712_type_hash(),
+            b256!("4b3ed10267f4afeecfb21fdff8753c23b5d479a989a433f64150787c6ce3e350")
+        );
+        let fill = eip712::SeatFill { offerDigest: Default::default(), seatIndex: 0 };
+        assert_eq!(fill.eip712_type_hash(), b256!("89bafd036f6be8d20c1d689e07ddd24ce79caf6e2995ceccb4f272833776b1f5"));
+        let slot = eip712::DualSignedSlotReveal {
+            battleKey: Default::default(),
+            turnId: 0,
+            committerMovesHash: Default::default(),
+            revealerSidePacked: U256::ZERO,
+        };
+        assert_eq!(slot.eip712_type_hash(), b256!("e4967167cd99f2b483c72c1fe6037d2653962173ef5ea9cb44d0f1a30d295b7a"));
+        let reveal = eip712::DualSignedReveal {
+            battleKey: Default::default(),
+            turnId: 0,
+            committerMoveHash: Default::default(),
+            revealerMoveIndex: 0,
+            revealerSalt: Default::default(),
+            revealerExtraData: 0,
+        };
+        assert_eq!(
+            reveal.eip712_type_hash(),
+            keccak256(
+                "DualSignedReveal(bytes32 battleKey,uint64 turnId,bytes32 committerMoveHash,uint8 revealerMoveIndex,\
+                 uint104 revealerSalt,uint16 revealerExtraData)"
+            )
+        );
+    }
+
+    #[test]
+    fn selectors_and_topics_follow_the_solidity_signatures() {
+        assert_eq!(IEngine::submitTurnMovesCall::SIGNATURE, "submitTurnMoves(bytes32,uint256,bytes32,bytes32)");
+        assert_eq!(
+            SignedMatchmaker::startGameCall::SIGNATURE,
+            "startGame(((address,uint96,address,uint96,address,uint96,address,uint96,address,address,address,address,address,address[]),uint256,uint8),uint8,bytes[4])"
+        );
+        assert_eq!(IEngine::MonMoves::SIGNATURE_HASH, keccak256("MonMoves(bytes32,uint256,uint256)"));
+        assert_eq!(IEngine::getMonStateForBattleCall::SIGNATURE, "getMonStateForBattle(bytes32,uint256,uint256,uint8)");
+    }
+
+    // Open seats are zeroed and team indices blinded before hashing, so a joiner's SeatFill binds
+    // the same offer whatever it later fills the seat with.
+    #[test]
+    fn offer_signing_form_blinds_indices_and_open_seats() {
+        let offer = BattleOffer {
+            battle: Battle {
+                p0: Address::repeat_byte(1),
+                p0TeamIndex: alloy_primitives::Uint::from(3),
+                p1: Address::repeat_byte(2),
+                ..Default::default()
+            },
+            pairHashNonce: U256::from(7),
+            battleMode: 0,
+        };
+        let form = offer_signing_form(&offer, 4);
+        assert_eq!(form.battle.p0, Address::repeat_byte(1));
+        assert_eq!(form.battle.p1, Address::ZERO);
+        assert_eq!(form.battle.p0TeamIndex, alloy_primitives::Uint::ZERO);
+        let domain = signed_matchmaker_domain(1, Address::repeat_byte(9));
+        assert_ne!(seat_digest(&domain, &offer, 4, 2), seat_digest(&domain, &offer, 4, 0));
+        assert_eq!(seat_digest(&domain, &offer, 0, 2), seat_digest(&domain, &offer, 0, 0));
+    }
+}
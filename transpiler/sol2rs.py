@@ -8,8 +8,12 @@ dependency knowledge) and emits a cargo workspace:
       Cargo.toml          (workspace; generated)
       engine/             (generated crate: one .rs per transpiled .sol)
       runtime/            (hand-written crate, synced from transpiler/runtime-rs)
+      bindings/           (hand-written crate, synced from transpiler/bindings-rs;
+                           alloy `sol!` ABI bindings for the deployed contracts)
       strategies/         (hand-written crate, synced from transpiler/strategies-rs;
                            carries the standalone `arena` + `trace` bins)
+      tools/              (hand-written crate, synced from transpiler/tools-rs;
+                           RPC tooling — log decoder, indexer, inspectors)
 
 Which files are emitted is governed by ``transpiler-config-rust.json``'s
 ``includeFiles`` allowlist — the Rust backend is being brought up phase by
@@ -39,7 +43,7 @@ WORKSPACE_CARGO_TOML = """\
 # Auto-generated by sol2rs — do not edit manually
 [workspace]
 resolver = "2"
-members = ["engine", "runtime", "strategies"]
+members = ["engine", "runtime", "bindings", "strategies", "tools"]
 
 # Solidity 0.8 checked arithmetic relies on native overflow panics in EVERY
 # profile. Do not turn this off: release builds would silently wrap where a
@@ -640,13 +644,15 @@ class SolidityToRustTranspiler:
     def _sync_crates(self) -> None:
         """Mirror the hand-written crates into the workspace.
 
-        transpiler/runtime-rs, strategies-rs are the git-tracked
-        sources of truth (rs-output is regenerated, exactly like ts-output's
-        runtime/ mirror)."""
+        transpiler/runtime-rs, bindings-rs, strategies-rs, tools-rs are the
+        git-tracked sources of truth (rs-output is regenerated, exactly like
+        ts-output's runtime/ mirror)."""
         base = Path(__file__).parent
         for src_name, dst_name in (
             ('runtime-rs', 'runtime'),
+            ('bindings-rs', 'bindings'),
             ('strategies-rs', 'strategies'),
+            ('tools-rs', 'tools'),
         ):
             src = base / src_name
             if not src.is_dir():
@@ -0,0 +1,69 @@
+//! Battle keys — `Engine.computeBattleKey` / `computePartyKey` re-derived off-chain, and the
+//! reverse lookup: a key is a keccak, so it can't be unpacked, but given the seats it was minted
+//! for, the nonce that produced it is found by scanning the pair's history.
+//!
+//! `pairHash = keccak256(abi.encode(lo, hi))` over the two seats in ascending address order
+//! (Multi: all four seats sorted → `partyHash`), then `battleKey = keccak256(abi.encode(pairHash,
+//! nonce))` where `nonce = pairHashNonces[pairHash]` at `startBattle` — the count of battles the
+//! same seats started before this one.
+
+use chomp_rt::{abi_encode, keccak256, Address, Token, B256, U256};
+
+/// Singles/Doubles pair hash; None for a self-battle (`InvalidBattleConfig`).
+pub fn pair_hash(p0: Address, p1: Address) -> Option<B256> {
+    if p0 == p1 {
+        return None;
+    }
+    let (lo, hi) = if p0 > p1 { (p1, p0) } else { (p0, p1) };
+    Some(keccak256(&abi_encode(&[Token::Address(lo), Token::Address(hi)])))
+}
+
+/// Multi party hash over all four seats; None when any two seats coincide.
+pub fn party_hash(seats: [Address; 4]) -> Option<B256> {
+    let mut s = seats;
+    s.sort();
+    if s.windows(2).any(|w| w[0] == w[1]) {
+        return None;
+    }
+    Some(keccak256(&abi_encode(&s.map(Token::Address))))
+}
+
+/// The battle key minted for the `nonce`-th battle between the seats behind `pair_hash`.
+pub fn battle_key(pair_hash: B256, nonce: u64) -> B256 {
+    keccak256(&abi_encode(&[Token::FixedBytes(pair_hash), Token::Uint(U256::from(nonce), 256)]))
+}
+
+/// The nonce below `max_nonce` under which `pair_hash` mints `key`, if any.
+pub fn find_nonce(key: B256, pair_hash: B256, max_nonce: u64) -> Option<u64> {
+    (0..max_nonce).find(|&n| battle_key(pair_hash, n) == key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arena::build_team_mon;
+    use crate::roster::{self, load_roster};
+    use crate::sim::{Sim, P0, P1};
+    use chomp_engine::Engine;
+
+    // The sim's battle was started at nonce 0, which bumped the pair's nonce — the engine's own
+    // getters must now agree with the re-derivation at nonce 1.
+    #[test]
+    fn matches_engine_key_derivation() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("..");
+        let roster = load_roster(&root);
+        let m = &roster.mons[0];
+        let mut sim = Sim::new(1, vec![build_team_mon(m)], vec![build_team_mon(m)], vec![m.id], vec![m.id], &roster::address_book());
+
+        let ph = pair_hash(P1, P0).unwrap();
+        assert_eq!(pair_hash(P0, P1), Some(ph));
+        assert_eq!(find_nonce(sim.battle_key, ph, 8), Some(0));
+        assert_eq!(Engine::computeBattleKey(&mut sim.world, P0, P1), (battle_key(ph, 1), ph));
+
+        let seats = [P1, Address::repeat_byte(9), P0, Address::repeat_byte(3)];
+        let party = party_hash(seats).unwrap();
+        assert_eq!(Engine::computePartyKey(&mut sim.world, seats[0], seats[1], seats[2], seats[3]), (battle_key(party, 0), party));
+        assert_eq!(pair_hash(P0, P0), None);
+        assert_eq!(party_hash([P0, P1, P0, Address::ZERO]), None);
+    }
+}
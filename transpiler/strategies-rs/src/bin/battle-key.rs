@@ -0,0 +1,64 @@
+//! Battle key helper — mint the key the engine assigns to a pair's (or Multi party's) Nth battle,
+//! or recover which battle between known seats an opaque key refers to. Exits 1 when no nonce in
+//! range produces the key.
+//!   cargo run --release -p chomp-strategies --bin battle-key -- derive --p0 0x… --p1 0x… [--nonce 3]
+//!   cargo run --release -p chomp-strategies --bin battle-key -- decode --key 0x… --p0 0x… --p1 0x… [--max-nonce 1000000]
+//!   (Multi: add --p2 0x… --p3 0x…)
+
+use chomp_rt::{Address, B256};
+use chomp_strategies::battlekey::{battle_key, find_nonce, pair_hash, party_hash};
+
+const USAGE: &str = "usage: battle-key <derive|decode> --p0 A --p1 B [--p2 C --p3 D] \
+[--nonce N] [--key K --max-nonce N]";
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("battle-key: {msg}\n{USAGE}");
+    std::process::exit(2);
+}
+
+fn arg_addr(args: &[String], flag: &str) -> Option<Address> {
+    let v = arg(args, flag)?;
+    Some(v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not an address: {v}"))))
+}
+
+fn arg_u(args: &[String], flag: &str, def: u64) -> u64 {
+    match arg(args, flag) {
+        Some(v) => v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not a number: {v}"))),
+        None => def,
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let cmd = args.get(1).map(String::as_str).unwrap_or_else(|| fail("missing command"));
+    let p0 = arg_addr(&args, "--p0").unwrap_or_else(|| fail("missing --p0"));
+    let p1 = arg_addr(&args, "--p1").unwrap_or_else(|| fail("missing --p1"));
+    let ph = match (arg_addr(&args, "--p2"), arg_addr(&args, "--p3")) {
+        (Some(p2), Some(p3)) => party_hash([p0, p1, p2, p3]),
+        (None, None) => pair_hash(p0, p1),
+        _ => fail("Multi needs both --p2 and --p3"),
+    }
+    .unwrap_or_else(|| fail("duplicate seat (the engine reverts InvalidBattleConfig)"));
+    eprintln!("pairHash {ph}");
+
+    match cmd {
+        "derive" => println!("{}", battle_key(ph, arg_u(&args, "--nonce", 0))),
+        "decode" => {
+            let v = arg(&args, "--key").unwrap_or_else(|| fail("missing --key"));
+            let key: B256 = v.parse().unwrap_or_else(|_| fail(&format!("--key: not a bytes32: {v}")));
+            let max = arg_u(&args, "--max-nonce", 1_000_000);
+            match find_nonce(key, ph, max) {
+                Some(n) => println!("nonce {n} (battle #{} between these seats)", n + 1),
+                None => {
+                    println!("NOT FOUND: no nonce below {max} mints this key for these seats");
+                    std::process::exit(1);
+                }
+            }
+        }
+        other => fail(&format!("unknown command {other:?}")),
+    }
+}
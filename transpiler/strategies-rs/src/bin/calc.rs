@@ -0,0 +1,103 @@
+//! Damage calc — the engine's damage range for one attacker → defender matchup (every catalog move,
+//! or just one), with optional single stat boosts. Fresh 1v1, both mons at full HP.
+//!   cargo run --release -p chomp-strategies --bin calc -- --attacker Ghouliath --defender Gorillax
+//!   cargo run --release -p chomp-strategies --bin calc -- --attacker 0 --defender 4 --move "Infernal Flame" --atk-pct 50
+
+use chomp_strategies::calc::{calc_damage, Boosts};
+use chomp_strategies::roster::{load_roster, Roster, RosterMon};
+use std::path::PathBuf;
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+fn arg_i(args: &[String], flag: &str, def: i32) -> i32 {
+    arg(args, flag).and_then(|v| v.parse().ok()).unwrap_or(def)
+}
+
+fn find_mon<'a>(roster: &'a Roster, key: &str) -> &'a RosterMon {
+    let found = match key.parse::<u32>() {
+        Ok(id) => roster.mon_by_id(id),
+        Err(_) => roster.mons.iter().find(|m| m.name.eq_ignore_ascii_case(key)),
+    };
+    found.unwrap_or_else(|| {
+        eprintln!("calc: no mon {key:?}");
+        std::process::exit(2);
+    })
+}
+
+fn pct(d: i64, hp: i64) -> f64 {
+    100.0 * d as f64 / hp.max(1) as f64
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let chomp_root = std::env::var("CHOMP_ROOT").map(PathBuf::from).unwrap_or_else(|_| {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("..")
+    });
+    let roster = load_roster(&chomp_root);
+    let (Some(a), Some(d)) = (arg(&args, "--attacker"), arg(&args, "--defender")) else {
+        eprintln!("usage: calc --attacker <mon> --defender <mon> [--move <name|lane>] [--atk-pct N] [--def-pct N]");
+        std::process::exit(2);
+    };
+    let (attacker, defender) = (find_mon(&roster, &a), find_mon(&roster, &d));
+    let boosts = Boosts { attack_pct: arg_i(&args, "--atk-pct", 0), defense_pct: arg_i(&args, "--def-pct", 0) };
+
+    let lanes: Vec<usize> = match arg(&args, "--move") {
+        Some(m) => {
+            let lane = m
+                .parse::<usize>()
+                .ok()
+                .filter(|&l| l < attacker.catalog.len())
+                .or_else(|| attacker.catalog.iter().position(|c| c.name.eq_ignore_ascii_case(&m)));
+            match lane {
+                Some(l) => vec![l],
+                None => {
+                    eprintln!("calc: {} has no move {m:?}", attacker.name);
+                    std::process::exit(2);
+                }
+            }
+        }
+        None => (0..attacker.catalog.len()).collect(),
+    };
+
+    eprintln!(
+        "calc: {} → {} · atk {:+}% · def {:+}% · fresh 1v1, full HP",
+        attacker.name, defender.name, boosts.attack_pct, boosts.defense_pct
+    );
+    println!(
+        "{:<3} {:<20} {:<17} {:>8} {:>4} {:>15} {:>15} {:>13} {:>5}",
+        "ln", "move", "type/class", "bp→eff", "acc", "damage", "crit", "%hp", "htko"
+    );
+    let mut defender_hp = 0;
+    let mut any_custom = false;
+    for lane in lanes {
+        let c = calc_damage(attacker, defender, lane, boosts);
+        defender_hp = c.defender_hp;
+        any_custom |= c.custom;
+        let kind = format!("{:?}/{:?}", c.move_type, c.move_class);
+        if !c.damaging() {
+            println!("{lane:<3} {:<20} {kind:<17} {:>8}", c.move_name, "—");
+            continue;
+        }
+        let htko = match (c.hits_to_ko(c.max), c.hits_to_ko(c.min)) {
+            (Some(lo), Some(hi)) if lo == hi => lo.to_string(),
+            (Some(lo), Some(hi)) => format!("{lo}-{hi}"),
+            _ => "∞".into(),
+        };
+        println!(
+            "{lane:<3} {:<20} {kind:<17} {:>8} {:>4} {:>15} {:>15} {:>13} {:>5}{}",
+            c.move_name,
+            format!("{}→{}", c.base_power, c.scaled_base_power),
+            c.accuracy,
+            format!("{}-{} ({})", c.min, c.max, c.base),
+            format!("{}-{} ({:.0}%)", c.crit_min, c.crit_max, c.crit_chance() * 100.0),
+            format!("{:.0}-{:.0}%", pct(c.min, c.defender_hp), pct(c.max, c.defender_hp)),
+            htko,
+            if c.custom { "  *" } else { "" }
+        );
+    }
+    println!("\n{} max HP {defender_hp}", defender.name);
+    if any_custom {
+        println!("* contract move: StandardAttack formula assumed, custom effects not modelled");
+    }
+}
@@ -0,0 +1,116 @@
+//! Commit-reveal helper — draw a reveal salt, compute a move commitment the engine will accept,
+//! check a reveal against an earlier commitment, or derive the turn rng two reveals produce,
+//! offline. Exits 1 when a reveal does not open its commitment.
+//!   cargo run --release -p chomp-strategies --bin commit -- salt [--move 2 --extra 0]
+//!   cargo run --release -p chomp-strategies --bin commit -- hash --move 2 --salt 0x1f2e --extra 0
+//!   cargo run --release -p chomp-strategies --bin commit -- verify --hash 0x… --move 2 --salt 0x1f2e
+//!   cargo run --release -p chomp-strategies --bin commit -- hash-side --m0 0 --e0 0 --m1 1 --e1 0 --salt 7
+//!   cargo run --release -p chomp-strategies --bin commit -- verify-side --hash 0x… --side 0x…
+//!   cargo run --release -p chomp-strategies --bin commit -- rng --p0 0x1f2e --p1 0x99
+
+use chomp_rt::{B256, U256};
+use chomp_strategies::commit::{
+    move_hash, move_preimage, random_salt, side_hash, turn_rng, verify_move, verify_side, SALT_BITS,
+};
+use chomp_strategies::sim::pack_side;
+
+const USAGE: &str = "usage: commit <salt|hash|verify|hash-side|verify-side|rng> [--hash H] \
+[--move M --salt S --extra E] [--side W | --m0 M --e0 E --m1 M --e1 E --salt S] [--p0 S --p1 S]";
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("commit: {msg}\n{USAGE}");
+    std::process::exit(2);
+}
+
+/// Required numeric flag (decimal or 0x-hex) that must fit in `bits`.
+fn arg_n(args: &[String], flag: &str, bits: u32, def: Option<u128>) -> u128 {
+    let v = match (arg(args, flag), def) {
+        (Some(v), _) => v,
+        (None, Some(d)) => return d,
+        (None, None) => fail(&format!("missing {flag}")),
+    };
+    let n = match v.strip_prefix("0x") {
+        Some(h) => u128::from_str_radix(h, 16),
+        None => v.parse(),
+    }
+    .unwrap_or_else(|_| fail(&format!("{flag}: not a number: {v}")));
+    if bits < 128 && n >> bits != 0 {
+        fail(&format!("{flag}: {v} does not fit in uint{bits}"));
+    }
+    n
+}
+
+fn arg_hash(args: &[String]) -> B256 {
+    let v = arg(args, "--hash").unwrap_or_else(|| fail("missing --hash"));
+    v.parse().unwrap_or_else(|_| fail(&format!("--hash: not a bytes32: {v}")))
+}
+
+/// The raw side word: `--side` verbatim, or assembled from the per-slot flags.
+fn side_word(args: &[String]) -> U256 {
+    if let Some(v) = arg(args, "--side") {
+        return v.parse().unwrap_or_else(|_| fail(&format!("--side: not a uint256: {v}")));
+    }
+    pack_side(
+        arg_n(args, "--m0", 8, None) as u8,
+        arg_n(args, "--e0", 16, Some(0)) as u16,
+        arg_n(args, "--m1", 8, None) as u8,
+        arg_n(args, "--e1", 16, Some(0)) as u16,
+        arg_n(args, "--salt", SALT_BITS, None),
+    )
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let cmd = args.get(1).map(String::as_str).unwrap_or_else(|| fail("missing command"));
+    let ok = match cmd {
+        "salt" => {
+            let salt = random_salt().unwrap_or_else(|e| fail(&format!("no entropy source: {e}")));
+            println!("{salt:#028x}");
+            if arg(&args, "--move").is_some() {
+                let mi = arg_n(&args, "--move", 8, None) as u8;
+                let extra = arg_n(&args, "--extra", 16, Some(0)) as u16;
+                println!("{}", move_hash(mi, salt, extra));
+            }
+            true
+        }
+        "rng" => {
+            let rng = turn_rng(arg_n(&args, "--p0", SALT_BITS, None), arg_n(&args, "--p1", SALT_BITS, None));
+            println!("{rng:#066x}");
+            true
+        }
+        "hash" | "verify" => {
+            let mi = arg_n(&args, "--move", 8, None) as u8;
+            let salt = arg_n(&args, "--salt", SALT_BITS, None);
+            let extra = arg_n(&args, "--extra", 16, Some(0)) as u16;
+            if cmd == "hash" {
+                let pre: String = move_preimage(mi, salt, extra).iter().map(|b| format!("{b:02x}")).collect();
+                eprintln!("preimage 0x{pre} (moveIndex {mi}, salt {salt:#x}, extraData {extra})");
+                println!("{}", move_hash(mi, salt, extra));
+                true
+            } else {
+                verify_move(arg_hash(&args), mi, salt, extra)
+            }
+        }
+        "hash-side" | "verify-side" => {
+            let side = side_word(&args);
+            if cmd == "hash-side" {
+                eprintln!("side word {side:#066x}");
+                println!("{}", side_hash(side));
+                true
+            } else {
+                verify_side(arg_hash(&args), side)
+            }
+        }
+        other => fail(&format!("unknown command {other:?}")),
+    };
+    if cmd.starts_with("verify") {
+        println!("{}", if ok { "MATCH" } else { "MISMATCH" });
+        if !ok {
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Calldata builder — raw transaction data for the battle entrypoints, printed as 0x-hex for a
+//! wallet or `cast send`. Built-in dual-signed buffer (`--digest` prints the EIP-712 digest the
+//! revealer signs instead; `--sig` takes the 65- or 64-byte signature back):
+//!   cargo run --release -p chomp-strategies --bin encode -- turn --key 0x… --move 2 --salt 7 [--extra 0] \
+//!       --reveal-move 1 --reveal-salt 9 [--reveal-extra 0] (--sig 0x… [--no-execute] | --digest --chain-id 1 \
+//!       --engine 0x… --turn 4)
+//!   cargo run --release -p chomp-strategies --bin encode -- slot-turn --key 0x… --m0 2 --m1 0 [--e0 0] [--e1 0] \
+//!       --salt 7 --reveal-m0 1 --reveal-m1 3 [--reveal-e0 0] [--reveal-e1 0] --reveal-salt 9 \
+//!       (--sig 0x… [--no-execute] | --digest --chain-id 1 --engine 0x… --turn 4)
+//! Signed matchmaking (seat order for `--sigs` / `--seat` is canonical: p0, p2, p1, p3):
+//!   cargo run --release -p chomp-strategies --bin encode -- start-game --p0 0x… --p1 0x… [--p2 0x… --p3 0x…] \
+//!       [--p0-team 0 … --p3-team 0] --registry 0x… [--rng-oracle 0x…] [--ruleset 0x…] \
+//!       --move-manager (0x…|builtin) --matchmaker 0x… [--hooks 0x…,0x…] --nonce 0 [--mode 0] [--open-seats 0] \
+//!       (--sigs 0x…,,0x…, | --digest --chain-id 1 --seat 2)
+//! LEGACY DefaultMatchmaker / DefaultCommitManager (p0's team is committed as `--team-hash`, derived from
+//! `--team-salt` + `--p0-mons`, or skipped with `--fast`; accept recomputes the integrity hash from the
+//! same terms):
+//!   cargo run --release -p chomp-strategies --bin encode -- propose --p0 0x… [--p1 0x…] --p0-team 0 \
+//!       (--team-hash 0x… | --team-salt 0x… --p0-mons 3,8,12 | --fast) --registry 0x… [--rng-oracle 0x…] \
+//!       [--ruleset 0x…] --move-manager 0x… --matchmaker 0x… [--hooks 0x…,0x…]
+//!   cargo run --release -p chomp-strategies --bin encode -- accept --key 0x… --p1-team 1 <propose's terms>
+//!   cargo run --release -p chomp-strategies --bin encode -- confirm --key 0x… --salt 0x… --p0-team 0
+//!   cargo run --release -p chomp-strategies --bin encode -- team-hash --team-salt 0x… --p0-team 0 --p0-mons 3,8,12
+//!   cargo run --release -p chomp-strategies --bin encode -- commit --key 0x… (--hash 0x… | --move 2 --salt 7 [--extra 0])
+//!   cargo run --release -p chomp-strategies --bin encode -- reveal --key 0x… --move 2 --salt 7 [--extra 0] [--no-execute]
+//!   cargo run --release -p chomp-strategies --bin encode -- switch --key 0x… --to 1 --salt 7 [--no-execute]
+//! Any command can come from JSON instead — `encode --json req.json` (or `-` for stdin) with
+//! `{"command": "turn", "key": "0x…", "move": 2, "sig": "0x…", "no-execute": true, …}`: keys are the
+//! flag names, arrays join into comma lists and `true` sets a bare flag.
+
+use chomp_engine::Constants::BUILTIN_DUAL_SIGNED_MANAGER;
+use chomp_rt::{Address, B256, U256};
+use chomp_strategies::calldata::{
+    accept_battle, commit_move, compact_signature, confirm_battle, integrity_hash, pack_turn_half, pack_turn_moves,
+    propose_battle, reveal_move, reveal_switch, seat_digest, slot_reveal_digest, start_game, submit_slot_turn_moves,
+    submit_turn_moves, team_hash, turn_reveal_digest, Battle, BattleOffer, ProposedBattle, FAST_BATTLE_SENTINAL_HASH,
+    SLOT_SALT_BITS, TEAM_INDEX_BITS,
+};
+use chomp_strategies::commit::{move_hash, SALT_BITS};
+use chomp_strategies::sim::pack_side;
+
+const USAGE: &str = "usage: encode <turn|slot-turn|start-game|propose|accept|confirm|team-hash|commit|reveal|switch> \
+                     [flags — see the file header] | encode --json <file|->";
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn has(args: &[String], flag: &str) -> bool {
+    args.iter().any(|a| a == flag)
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("encode: {msg}\n{USAGE}");
+    std::process::exit(2);
+}
+
+fn parse_n(flag: &str, v: &str, bits: u32) -> u128 {
+    let n = match v.strip_prefix("0x") {
+        Some(h) => u128::from_str_radix(h, 16),
+        None => v.parse(),
+    }
+    .unwrap_or_else(|_| fail(&format!("{flag}: not a number: {v}")));
+    if bits < 128 && n >> bits != 0 {
+        fail(&format!("{flag}: {v} does not fit in uint{bits}"));
+    }
+    n
+}
+
+/// Numeric flag (decimal or 0x-hex) that must fit in `bits`; `def` = None makes it required.
+fn arg_n(args: &[String], flag: &str, bits: u32, def: Option<u128>) -> u128 {
+    match (arg(args, flag), def) {
+        (Some(v), _) => parse_n(flag, &v, bits),
+        (None, Some(d)) => d,
+        (None, None) => fail(&format!("missing {flag}")),
+    }
+}
+
+fn arg_b256(args: &[String], flag: &str) -> B256 {
+    let v = arg(args, flag).unwrap_or_else(|| fail(&format!("missing {flag}")));
+    v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not a bytes32: {v}")))
+}
+
+fn parse_addr(flag: &str, v: &str) -> Address {
+    v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not an address: {v}")))
+}
+
+fn arg_addr(args: &[String], flag: &str) -> Address {
+    let v = arg(args, flag).unwrap_or_else(|| fail(&format!("missing {flag}")));
+    parse_addr(flag, &v)
+}
+
+/// Address flag where address(0) means something (open proposal, inline keccak rng, no ruleset).
+fn arg_addr_or_zero(args: &[String], flag: &str) -> Address {
+    arg(args, flag).map(|v| parse_addr(flag, &v)).unwrap_or(Address::ZERO)
+}
+
+fn parse_hex(flag: &str, v: &str) -> Vec<u8> {
+    let h = v.strip_prefix("0x").unwrap_or(v);
+    if !h.len().is_multiple_of(2) {
+        fail(&format!("{flag}: odd-length hex: {v}"));
+    }
+    (0..h.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&h[i..i + 2], 16).unwrap_or_else(|_| fail(&format!("{flag}: not hex: {v}"))))
+        .collect()
+}
+
+fn arg_sig(args: &[String]) -> (B256, B256) {
+    let v = arg(args, "--sig").unwrap_or_else(|| fail("missing --sig (or pass --digest to print what to sign)"));
+    compact_signature(&parse_hex("--sig", &v))
+        .unwrap_or_else(|| fail("--sig: want a 65-byte (r, s, v) or 64-byte (r, vs) signature"))
+}
+
+fn arg_list(args: &[String], flag: &str) -> Vec<String> {
+    arg(args, flag).map(|l| l.split(',').map(|s| s.trim().to_string()).collect()).unwrap_or_default()
+}
+
+fn arg_hooks(args: &[String]) -> Vec<Address> {
+    arg_list(args, "--hooks").iter().map(|h| parse_addr("--hooks", h)).collect()
+}
+
+fn arg_move_manager(args: &[String]) -> Address {
+    match arg(args, "--move-manager").as_deref() {
+        Some("builtin") => BUILTIN_DUAL_SIGNED_MANAGER,
+        _ => arg_addr(args, "--move-manager"),
+    }
+}
+
+/// `--p0-mons`: the registry mon ids `getMonRegistryIndicesForTeam(p0, p0TeamIndex)` returns.
+fn arg_team_hash_from_salt(args: &[String]) -> B256 {
+    let mons: Vec<U256> =
+        arg_list(args, "--p0-mons").iter().map(|m| U256::from(parse_n("--p0-mons", m, 128))).collect();
+    if mons.is_empty() {
+        fail("missing --p0-mons");
+    }
+    team_hash(arg_b256(args, "--team-salt"), arg_n(args, "--p0-team", TEAM_INDEX_BITS as u32, None), &mons)
+}
+
+/// The proposal terms `acceptBattle`'s integrity hash covers, shared by propose and accept.
+fn proposal_terms(args: &[String]) -> ProposedBattle {
+    let p0_team_hash = if has(args, "--fast") {
+        FAST_BATTLE_SENTINAL_HASH
+    } else if arg(args, "--team-salt").is_some() {
+        arg_team_hash_from_salt(args)
+    } else if arg(args, "--team-hash").is_some() {
+        arg_b256(args, "--team-hash")
+    } else {
+        fail("p0's team needs --team-hash, --team-salt with --p0-mons, or --fast")
+    };
+    ProposedBattle {
+        p0_team_hash,
+        team_registry: arg_addr(args, "--registry"),
+        rng_oracle: arg_addr_or_zero(args, "--rng-oracle"),
+        ruleset: arg_addr_or_zero(args, "--ruleset"),
+        move_manager: arg_move_manager(args),
+        matchmaker: arg_addr(args, "--matchmaker"),
+        engine_hooks: arg_hooks(args),
+        ..Default::default()
+    }
+}
+
+/// A JSON request object flattened back into the argv the flag parsers read.
+fn json_args(path: &str) -> Vec<String> {
+    let text = if path == "-" { std::io::read_to_string(std::io::stdin()) } else { std::fs::read_to_string(path) }
+        .unwrap_or_else(|e| fail(&format!("--json {path}: {e}")));
+    let req: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&text).unwrap_or_else(|e| fail(&format!("--json {path}: {e}")));
+    let scalar = |k: &str, v: &serde_json::Value| match v {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => n.to_string(),
+        _ => fail(&format!("--json: {k}: want a string or number, got {v}")),
+    };
+    let mut args = vec!["encode".to_string()];
+    match req.get("command") {
+        Some(serde_json::Value::String(c)) => args.push(c.clone()),
+        _ => fail("--json: missing \"command\""),
+    }
+    for (k, v) in &req {
+        if k == "command" {
+            continue;
+        }
+        match v {
+            serde_json::Value::Bool(true) => args.push(format!("--{k}")),
+            serde_json::Value::Bool(false) | serde_json::Value::Null => {}
+            serde_json::Value::Array(items) => {
+                args.push(format!("--{k}"));
+                args.push(items.iter().map(|i| scalar(k, i)).collect::<Vec<_>>().join(","));
+            }
+            _ => {
+                args.push(format!("--{k}"));
+                args.push(scalar(k, v));
+            }
+        }
+    }
+    args
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let h: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("0x{h}")
+}
+
+fn main() {
+    let mut args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("--json") {
+        args = json_args(args.get(2).map(String::as_str).unwrap_or_else(|| fail("--json needs a path or -")));
+    }
+    let args = args;
+    let cmd = args.get(1).map(String::as_str).unwrap_or_else(|| fail("missing command"));
+    let execute = !has(&args, "--no-execute");
+    let digest = has(&args, "--digest");
+    let team = |flag| arg_n(&args, flag, TEAM_INDEX_BITS as u32, None);
+    let team_or_zero = |flag| arg_n(&args, flag, TEAM_INDEX_BITS as u32, Some(0));
+    let chain_id = || arg_n(&args, "--chain-id", 64, None) as u64;
+
+    let data = match cmd {
+        "turn" => {
+            let packed = pack_turn_moves(
+                pack_turn_half(
+                    arg_n(&args, "--move", 8, None) as u8,
+                    arg_n(&args, "--salt", SALT_BITS, None),
+                    arg_n(&args, "--extra", 16, Some(0)) as u16,
+                ),
+                pack_turn_half(
+                    arg_n(&args, "--reveal-move", 8, None) as u8,
+                    arg_n(&args, "--reveal-salt", SALT_BITS, None),
+                    arg_n(&args, "--reveal-extra", 16, Some(0)) as u16,
+                ),
+            );
+            let key = arg_b256(&args, "--key");
+            if digest {
+                let turn = arg_n(&args, "--turn", 64, None) as u64;
+                turn_reveal_digest(chain_id(), arg_addr(&args, "--engine"), key, turn, packed).to_vec()
+            } else {
+                let (r, vs) = arg_sig(&args);
+                submit_turn_moves(key, packed, r, vs, execute)
+            }
+        }
+        "slot-turn" => {
+            let side = |prefix: &str| {
+                let n = |f: &str, bits| arg_n(&args, &format!("--{prefix}{f}"), bits, None);
+                let extra = |f: &str| arg_n(&args, &format!("--{prefix}{f}"), 16, Some(0)) as u16;
+                pack_side(n("m0", 8) as u8, extra("e0"), n("m1", 8) as u8, extra("e1"), n("salt", SLOT_SALT_BITS))
+            };
+            let (committer, revealer) = (side(""), side("reveal-"));
+            let key = arg_b256(&args, "--key");
+            if digest {
+                let turn = arg_n(&args, "--turn", 64, None) as u64;
+                slot_reveal_digest(chain_id(), arg_addr(&args, "--engine"), key, turn, committer, revealer).to_vec()
+            } else {
+                let (r, vs) = arg_sig(&args);
+                submit_slot_turn_moves(key, committer, revealer, r, vs, execute)
+            }
+        }
+        "start-game" => {
+            let offer = BattleOffer {
+                battle: Battle {
+                    p0: arg_addr(&args, "--p0"),
+                    p0_team_index: team_or_zero("--p0-team"),
+                    p1: arg_addr_or_zero(&args, "--p1"),
+                    p1_team_index: team_or_zero("--p1-team"),
+                    p2: arg_addr_or_zero(&args, "--p2"),
+                    p2_team_index: team_or_zero("--p2-team"),
+                    p3: arg_addr_or_zero(&args, "--p3"),
+                    p3_team_index: team_or_zero("--p3-team"),
+                    team_registry: arg_addr(&args, "--registry"),
+                    rng_oracle: arg_addr_or_zero(&args, "--rng-oracle"),
+                    ruleset: arg_addr_or_zero(&args, "--ruleset"),
+                    move_manager: arg_move_manager(&args),
+                    matchmaker: arg_addr(&args, "--matchmaker"),
+                    engine_hooks: arg_hooks(&args),
+                },
+                pair_hash_nonce: U256::from(arg_n(&args, "--nonce", 128, None)),
+                battle_mode: arg_n(&args, "--mode", 8, Some(0)) as u8,
+            };
+            let open = arg_n(&args, "--open-seats", 4, Some(0)) as u8;
+            if digest {
+                seat_digest(chain_id(), &offer, open, arg_n(&args, "--seat", 2, None) as u8).to_vec()
+            } else {
+                let sigs = arg_list(&args, "--sigs");
+                if sigs.len() > 4 {
+                    fail("--sigs: at most four seats");
+                }
+                let mut seat_sigs: [Vec<u8>; 4] = Default::default();
+                for (slot, sig) in seat_sigs.iter_mut().zip(&sigs) {
+                    *slot = parse_hex("--sigs", sig);
+                }
+                start_game(&offer, open, &seat_sigs)
+            }
+        }
+        "propose" => propose_battle(&ProposedBattle {
+            p0: arg_addr(&args, "--p0"),
+            p0_team_index: team("--p0-team"),
+            p1: arg_addr_or_zero(&args, "--p1"),
+            ..proposal_terms(&args)
+        }),
+        "accept" => accept_battle(arg_b256(&args, "--key"), team("--p1-team"), integrity_hash(&proposal_terms(&args))),
+        "confirm" => confirm_battle(arg_b256(&args, "--key"), arg_b256(&args, "--salt"), team("--p0-team")),
+        "team-hash" => arg_team_hash_from_salt(&args).to_vec(),
+        "commit" => {
+            let hash = match arg(&args, "--hash") {
+                Some(_) => arg_b256(&args, "--hash"),
+                None => move_hash(
+                    arg_n(&args, "--move", 8, None) as u8,
+                    arg_n(&args, "--salt", SALT_BITS, None),
+                    arg_n(&args, "--extra", 16, Some(0)) as u16,
+                ),
+            };
+            commit_move(arg_b256(&args, "--key"), hash)
+        }
+        "reveal" => reveal_move(
+            arg_b256(&args, "--key"),
+            arg_n(&args, "--move", 8, None) as u8,
+            arg_n(&args, "--salt", SALT_BITS, None),
+            arg_n(&args, "--extra", 16, Some(0)) as u16,
+            execute,
+        ),
+        "switch" => reveal_switch(
+            arg_b256(&args, "--key"),
+            arg_n(&args, "--to", 16, None) as u16,
+            arg_n(&args, "--salt", SALT_BITS, None),
+            execute,
+        ),
+        other => fail(&format!("unknown command {other:?}")),
+    };
+    println!("{}", hex(&data));
+}
@@ -0,0 +1,50 @@
+//! Team check — list every registry rule a proposed team breaks before it's registered on-chain.
+//! Exits 1 on any violation.
+//!   cargo run --release -p chomp-strategies --bin team-check -- team.json
+//!   cargo run --release -p chomp-strategies --bin team-check -- --team Ghouliath,3,Xmon,Sofabbi
+//!
+//! team.json: `{"mons": [0, "Inutia", {"mon": "Xmon", "moves": [0, 1, 2, 5], "level": 6}, …]}` —
+//! bare refs take the default loadout; `moves` are catalog lanes or move names.
+
+use chomp_strategies::roster::load_roster;
+use chomp_strategies::teamcheck::{check_team, Entry, ProposedTeam, Ref};
+use std::path::PathBuf;
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let team: ProposedTeam = if let Some(list) = arg(&args, "--team") {
+        let mons = list
+            .split(',')
+            .map(|s| s.trim())
+            .map(|s| Entry::Bare(s.parse().map(Ref::Index).unwrap_or_else(|_| Ref::Name(s.to_string()))))
+            .collect();
+        ProposedTeam { mons }
+    } else if let Some(path) = args.get(1) {
+        let text = std::fs::read_to_string(path).unwrap_or_else(|e| panic!("read {path}: {e}"));
+        serde_json::from_str(&text).unwrap_or_else(|e| panic!("parse {path}: {e}"))
+    } else {
+        eprintln!("usage: team-check <team.json> | --team <mon,mon,…>");
+        std::process::exit(2);
+    };
+
+    let chomp_root = std::env::var("CHOMP_ROOT").map(PathBuf::from).unwrap_or_else(|_| {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("..")
+    });
+    let roster = load_roster(&chomp_root);
+
+    let violations = check_team(&roster, &team);
+    if violations.is_empty() {
+        println!("OK: {} mons, registry would accept this team", team.mons.len());
+        return;
+    }
+    for v in &violations {
+        let at = v.slot.map(|s| format!("slot {s}")).unwrap_or_else(|| "team".into());
+        println!("{:<8} {:<22} {}", at, v.error, v.detail);
+    }
+    println!("\n{} violation(s)", violations.len());
+    std::process::exit(1);
+}
@@ -0,0 +1,135 @@
+//! Turn order — who moves first for a p0 / p1 matchup, asked of the engine's own priority
+//! resolution. With both actions given, prints the verdict and what decided it; otherwise a grid
+//! of every p0 action (catalog lanes + switch) against every p1 action.
+//!   cargo run --release -p chomp-strategies --bin turn-order -- --p0 Ghouliath --p1 Gorillax
+//!   cargo run --release -p chomp-strategies --bin turn-order -- --p0 0 --p1 4 --p0-move 1 --p1-move switch --p0-speed-pct 50
+//!   cargo run --release -p chomp-strategies --bin turn-order -- --p0 0 --p1 0 --p0-move 0 --p1-move 0 --p0-salt 7 --p1-salt 9
+
+use chomp_strategies::commit::{turn_rng, SALT_BITS};
+use chomp_strategies::roster::{load_roster, Roster, RosterMon};
+use chomp_strategies::turnorder::{turn_order, Action, Side};
+use std::path::PathBuf;
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+fn arg_i(args: &[String], flag: &str, def: i32) -> i32 {
+    arg(args, flag).and_then(|v| v.parse().ok()).unwrap_or(def)
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("turn-order: {msg}");
+    std::process::exit(2);
+}
+
+fn find_mon<'a>(roster: &'a Roster, key: &str) -> &'a RosterMon {
+    let found = match key.parse::<u32>() {
+        Ok(id) => roster.mon_by_id(id),
+        Err(_) => roster.mons.iter().find(|m| m.name.eq_ignore_ascii_case(key)),
+    };
+    found.unwrap_or_else(|| fail(&format!("no mon {key:?}")))
+}
+
+fn parse_action(mon: &RosterMon, key: &str) -> Action {
+    if key.eq_ignore_ascii_case("switch") {
+        return Action::Switch;
+    }
+    key.parse::<usize>()
+        .ok()
+        .filter(|&l| l < mon.catalog.len())
+        .or_else(|| mon.catalog.iter().position(|c| c.name.eq_ignore_ascii_case(key)))
+        .map(Action::Lane)
+        .unwrap_or_else(|| fail(&format!("{} has no move {key:?}", mon.name)))
+}
+
+fn action_name(mon: &RosterMon, a: Action) -> String {
+    match a {
+        Action::Lane(l) => mon.catalog[l].name.clone(),
+        Action::Switch => "switch".into(),
+    }
+}
+
+fn salt(args: &[String], flag: &str) -> Option<u128> {
+    let v = arg(args, flag)?;
+    let n = match v.strip_prefix("0x") {
+        Some(h) => u128::from_str_radix(h, 16),
+        None => v.parse(),
+    }
+    .unwrap_or_else(|_| fail(&format!("{flag}: not a number: {v}")));
+    if n >> SALT_BITS != 0 {
+        fail(&format!("{flag}: {v} does not fit in uint{SALT_BITS}"));
+    }
+    Some(n)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let chomp_root = std::env::var("CHOMP_ROOT").map(PathBuf::from).unwrap_or_else(|_| {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("..")
+    });
+    let roster = load_roster(&chomp_root);
+    let (Some(a), Some(b)) = (arg(&args, "--p0"), arg(&args, "--p1")) else {
+        eprintln!(
+            "usage: turn-order --p0 <mon> --p1 <mon> [--p0-move <lane|name|switch>] [--p1-move …] \
+             [--p0-speed-pct N] [--p1-speed-pct N] [--p0-salt S --p1-salt S]"
+        );
+        std::process::exit(2);
+    };
+    let mons = [find_mon(&roster, &a), find_mon(&roster, &b)];
+    let pcts = [arg_i(&args, "--p0-speed-pct", 0), arg_i(&args, "--p1-speed-pct", 0)];
+    let side = |p: usize, action| Side { mon: mons[p], action, speed_pct: pcts[p] };
+
+    let given = [arg(&args, "--p0-move"), arg(&args, "--p1-move")];
+    if let [Some(m0), Some(m1)] = &given {
+        let acts = [parse_action(mons[0], m0), parse_action(mons[1], m1)];
+        let o = turn_order(&side(0, acts[0]), &side(1, acts[1]));
+        for p in 0..2 {
+            println!(
+                "p{p} {:<12} {:<20} priority {}  speed {}",
+                mons[p].name,
+                action_name(mons[p], acts[p]),
+                o.priority[p],
+                o.speed[p]
+            );
+        }
+        match (o.first, salt(&args, "--p0-salt"), salt(&args, "--p1-salt")) {
+            (Some(p), _, _) => println!("p{p} moves first (by {})", o.decided_by()),
+            (None, Some(s0), Some(s1)) => {
+                let rng = turn_rng(s0, s1);
+                println!("speed tie: turn rng {rng:#x} → p{} moves first", o.resolve(rng));
+            }
+            (None, _, _) => println!("speed tie: 50/50 on the turn rng (pass --p0-salt/--p1-salt to settle)"),
+        }
+        return;
+    }
+
+    // Grid: rows = p0 actions, cols = p1 actions (restricted to a given move on either side).
+    let all = |p: usize| -> Vec<Action> {
+        match &given[p] {
+            Some(m) => vec![parse_action(mons[p], m)],
+            None => (0..mons[p].catalog.len()).map(Action::Lane).chain([Action::Switch]).collect(),
+        }
+    };
+    let (rows, cols) = (all(0), all(1));
+    eprintln!(
+        "turn-order: p0 {} (speed {:+}%) vs p1 {} (speed {:+}%) · cell = first mover",
+        mons[0].name, pcts[0], mons[1].name, pcts[1]
+    );
+    print!("{:<20}", "p0 \\ p1");
+    for &c in &cols {
+        print!(" {:>14.14}", action_name(mons[1], c));
+    }
+    println!();
+    for &r in &rows {
+        print!("{:<20.20}", action_name(mons[0], r));
+        for &c in &cols {
+            let o = turn_order(&side(0, r), &side(1, c));
+            let cell = match o.first {
+                Some(p) => format!("p{p} ({})", o.decided_by()),
+                None => "50/50".into(),
+            };
+            print!(" {cell:>14}");
+        }
+        println!();
+    }
+}
@@ -0,0 +1,310 @@
+//! Offline damage calc — the engine's own damage math over a roster matchup, without a chain.
+//!
+//! Everything funnels through the transpiled `AttackCalculator._calculateDamageCore` (the one
+//! formula both the inline and external attack paths share) with a synthetic roll word `h`
+//! steered onto each extreme — min/max volatility, crit/no-crit — so the range is exact by
+//! construction rather than re-derived. Inline moves use the engine's inline-attack defaults
+//! (`DEFAULT_ACCURACY` / `DEFAULT_VOL` / `DEFAULT_CRIT_RATE`); contract moves report their probed
+//! basePower/accuracy/volatility/critRate (defaults only where a getter is absent), which is exact
+//! for `StandardAttack` moves and only an estimate for custom ones (flagged via
+//! [`DamageCalc::custom`]).
+
+use chomp_engine::moves::{AttackCalculator, MoveSlotLib};
+use chomp_engine::types::TypeCalcLib;
+use chomp_engine::Constants::{DEFAULT_ACCURACY, DEFAULT_CRIT_RATE, DEFAULT_VOL};
+use chomp_engine::Enums::{MoveClass, Type};
+use chomp_engine::Structs::DamageCalcContext;
+use chomp_rt::U256;
+
+use crate::arena::build_team_mon;
+use crate::roster::{self, RosterMon};
+use crate::shared::build_damage_calc_context;
+use crate::sim::Sim;
+use crate::view::{
+    decode_meta, mon_max_hp, slot_external_accuracy, slot_external_crit_rate, slot_external_volatility, Seat, VCPU,
+    VOPP,
+};
+
+/// Single stat boosts as % of the base stat (the delta one StatBoosts application writes).
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Boosts {
+    pub attack_pct: i32,
+    pub defense_pct: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct DamageCalc {
+    pub move_name: String,
+    pub move_type: Type,
+    pub move_class: MoveClass,
+    pub base_power: u32,
+    /// basePower after type effectiveness against both defender types (0 = immune).
+    pub scaled_base_power: u32,
+    pub accuracy: u32,
+    pub volatility: u32,
+    pub crit_rate: u32,
+    /// Non-crit damage at the low / neutral / high volatility roll.
+    pub min: i64,
+    pub base: i64,
+    pub max: i64,
+    /// Crit damage at the low / high volatility roll.
+    pub crit_min: i64,
+    pub crit_max: i64,
+    pub defender_hp: i64,
+    /// Contract move that isn't guaranteed to follow the StandardAttack formula.
+    pub custom: bool,
+}
+
+impl DamageCalc {
+    pub fn damaging(&self) -> bool {
+        (self.move_class == MoveClass::Physical || self.move_class == MoveClass::Special) && self.base_power > 0
+    }
+
+    /// Probability a hit crits: the roll crits when `critRoll % 100 <= critRate`.
+    pub fn crit_chance(&self) -> f64 {
+        (self.crit_rate.min(99) + 1) as f64 / 100.0
+    }
+
+    /// Hits needed to KO from full HP at a given per-hit damage (None = can't damage).
+    pub fn hits_to_ko(&self, per_hit: i64) -> Option<i64> {
+        if per_hit <= 0 {
+            None
+        } else {
+            Some((self.defender_hp + per_hit - 1) / per_hit)
+        }
+    }
+}
+
+/// A scaling roll (`uint64(h >> 64)`) landing on the requested side of the `r % 100 > 50` split
+/// with `r % (vol+1)` as large as that side allows — `vol` itself, except on the low side when
+/// `vol+1` is a multiple of 100: every `r ≡ vol` then has `r % 100 == 99`, and the furthest the
+/// low side reaches is `vol - 49`. Solved by CRT over `r ≡ x (mod vol+1)`, `r % 100 == t`.
+fn scaling_roll(volatility: u32, high: bool) -> u64 {
+    let m = volatility as u64 + 1;
+    let g = gcd(m, 100);
+    let x = if !high && g == 100 { m - 50 } else { m - 1 };
+    // `t` must share `x`'s residue mod g; both sides span ≥ 49 consecutive values, so one exists.
+    let t = if high { 51 + (x + g - 51 % g) % g } else { x % g };
+    let n = 100 / g;
+    let k = ((t + 100 - x % 100) % 100 / g) * inverse_mod((m / g) % n, n) % n;
+    x + m * k
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// `a⁻¹ mod n` for coprime `a`, `n` (0 when `n == 1`).
+fn inverse_mod(a: u64, n: u64) -> u64 {
+    let (mut r0, mut r1, mut s0, mut s1) = (n as i64, a as i64, 0i64, 1i64);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, s0 - q * s1);
+    }
+    s0.rem_euclid(n as i64) as u64
+}
+
+fn core(ctx: &mut DamageCalcContext, scaled: u32, class: MoveClass, vol: u32, scaling: u64, crit: bool, crit_rate: u32) -> i64 {
+    let crit_roll: u64 = if crit { 0 } else { 99 };
+    let h = (U256::from(crit_roll) << 128) | (U256::from(scaling) << 64);
+    let (damage, _) = AttackCalculator::_calculateDamageCore(ctx, scaled, class, U256::from(vol), h, U256::from(crit_rate));
+    damage.max(0) as i64
+}
+
+/// Damage range for `attacker` using catalog lane `lane` into `defender` (both at full HP, fresh
+/// 1v1, optional single boosts). Lanes outside the default loadout are fine — the word is decoded
+/// directly, not played.
+pub fn calc_damage(attacker: &RosterMon, defender: &RosterMon, lane: usize, boosts: Boosts) -> DamageCalc {
+    let book = roster::address_book();
+    let obs = Seat { cpu: 1 };
+    // Attacker = p0 (VOPP slot 0), defender = p1 (VCPU slot 0), as in the static matrix.
+    let mut sim = Sim::new(
+        1,
+        vec![build_team_mon(attacker)],
+        vec![build_team_mon(defender)],
+        vec![attacker.id],
+        vec![defender.id],
+        &book,
+    );
+    let bk = sim.battle_key;
+    let cm = &attacker.catalog[lane];
+    let meta = decode_meta(&mut sim, bk, VOPP, 0, cm.word);
+    let inline = MoveSlotLib::isInline(cm.word);
+    let (accuracy, vol, crit_rate) = if inline {
+        (DEFAULT_ACCURACY, DEFAULT_VOL, DEFAULT_CRIT_RATE)
+    } else {
+        (
+            slot_external_accuracy(&mut sim, bk, cm.word).unwrap_or(DEFAULT_ACCURACY),
+            slot_external_volatility(&mut sim, bk, cm.word).unwrap_or(DEFAULT_VOL),
+            slot_external_crit_rate(&mut sim, bk, cm.word).unwrap_or(DEFAULT_CRIT_RATE),
+        )
+    };
+
+    let mut ctx = build_damage_calc_context(&mut sim, obs, bk, VOPP, 0, VCPU, 0);
+    ctx.attackerAttackDelta = ctx.attackerAttack as i32 * boosts.attack_pct / 100;
+    ctx.attackerSpAtkDelta = ctx.attackerSpAtk as i32 * boosts.attack_pct / 100;
+    ctx.defenderDefDelta = ctx.defenderDef as i32 * boosts.defense_pct / 100;
+    ctx.defenderSpDefDelta = ctx.defenderSpDef as i32 * boosts.defense_pct / 100;
+
+    // Same two-step type scaling as `_calculateDamageFromContext`.
+    let mut scaled = TypeCalcLib::getTypeEffectiveness(meta.moveType, ctx.defenderType1, meta.basePower);
+    if ctx.defenderType2 != Type::None {
+        scaled = TypeCalcLib::getTypeEffectiveness(meta.moveType, ctx.defenderType2, scaled);
+    }
+
+    let class = meta.moveClass;
+    let (lo, hi) = (scaling_roll(vol, false), scaling_roll(vol, true));
+    let neutral = 0; // r % (vol+1) == 0 → rngScaling 100
+    DamageCalc {
+        move_name: cm.name.clone(),
+        move_type: meta.moveType,
+        move_class: class,
+        base_power: meta.basePower,
+        scaled_base_power: scaled,
+        accuracy,
+        volatility: vol,
+        crit_rate,
+        min: core(&mut ctx, scaled, class, vol, lo, false, crit_rate),
+        base: core(&mut ctx, scaled, class, vol, neutral, false, crit_rate),
+        max: core(&mut ctx, scaled, class, vol, hi, false, crit_rate),
+        crit_min: core(&mut ctx, scaled, class, vol, lo, true, crit_rate),
+        crit_max: core(&mut ctx, scaled, class, vol, hi, true, crit_rate),
+        defender_hp: mon_max_hp(&mut sim, obs, bk, VCPU, 0),
+        custom: !inline,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commit::turn_rng;
+    use crate::roster::load_roster;
+    use crate::shared::{estimate_damage, get_move_base_power};
+    use crate::view::{mon_current_hp, NO_OP_INDEX, SWITCH_MOVE_INDEX};
+
+    // The neutral roll is the deterministic estimator's (vol 0 ⇒ scaling 100), so across every
+    // ordered pair's default damaging moves the calc's `base` must equal the CPU's estimate,
+    // which also pins that the estimator's fixed rng (50) rolls no crit.
+    // Custom contract moves without a `basePower` probe read as 0 to the estimator — skipped.
+    #[test]
+    fn neutral_roll_matches_cpu_estimate() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("..");
+        let roster = load_roster(&root);
+        let book = roster::address_book();
+        let obs = Seat { cpu: 1 };
+        for a in &roster.mons {
+            for d in &roster.mons {
+                let mut sim = Sim::new(1, vec![build_team_mon(a)], vec![build_team_mon(d)], vec![a.id], vec![d.id], &book);
+                let bk = sim.battle_key;
+                let mut ctx = build_damage_calc_context(&mut sim, obs, bk, VOPP, 0, VCPU, 0);
+                for lane in 0..a.catalog.len().min(4) {
+                    let c = calc_damage(a, d, lane, Boosts::default());
+                    if !c.damaging() || get_move_base_power(&mut sim, bk, a.catalog[lane].word) != c.base_power as i64 {
+                        continue;
+                    }
+                    let est = estimate_damage(&mut sim, bk, &mut ctx, a.catalog[lane].word, c.move_class);
+                    assert_eq!(c.base, est, "{} {} -> {}", a.name, c.move_name, d.name);
+                    assert!(c.min <= c.base && c.base <= c.max && c.max <= c.crit_max);
+                }
+            }
+        }
+    }
+
+    // Against brute force over a full period of both moduli: the roll sits on the asked side
+    // of the split and reaches the largest `r % (vol+1)` any roll on that side does.
+    #[test]
+    fn scaling_roll_reaches_each_sides_extreme() {
+        for vol in 0..=250u32 {
+            let m = vol as u64 + 1;
+            for high in [false, true] {
+                let r = scaling_roll(vol, high);
+                assert_eq!(r % 100 > 50, high, "vol {vol} high {high}: roll {r}");
+                let best = (0..100 * m).filter(|r| (r % 100 > 50) == high).map(|r| r % m).max().unwrap();
+                assert_eq!(r % m, best, "vol {vol} high {high}: roll {r}");
+            }
+        }
+    }
+
+    // Engine trace: both leads sent in, then one real turn with fixed salts where the attacker
+    // uses the lane and the defender idles. The inline path rolls
+    // `h = mixRngForAttacker(turn_rng(p0Salt, p1Salt), 0)`, so the defender's HP loss must land
+    // in the calc's range for that roll's crit outcome (or be 0 on a miss). Turns where a
+    // switch-in ability already moved a stat the formula reads (Interweaving's ATK drop) aren't
+    // the calc's clean 1v1, so they're skipped.
+    //
+    // Contract moves answering the StandardAttack getters (Infernal Flame, Wither Away, …) go
+    // through `dispatchStandardAttack`, which rolls the same `h`, so they're held to the same
+    // range. Their on-hit statuses keep acting at round end (a burn tick, Panic's stamina drain
+    // setting off Dreamcatcher's heal), so a second turn where both sides idle measures that
+    // per-round HP change and it's taken back out of the attack turn's loss. Bubble Bop hits
+    // twice per use, so its loss isn't one roll of the formula; and Xmon's Dreamcatcher heals off
+    // any stamina gain, which those moves' stamina side effects (Vital Siphon's steal, Panic's
+    // drain refilled by regen) set off unevenly across the two turns — both are left out.
+    #[test]
+    fn real_turn_damage_lies_in_calc_range() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("..");
+        let roster = load_roster(&root);
+        let book = roster::address_book();
+        let obs = Seat { cpu: 1 };
+        let mut bad = Vec::new();
+        let mut n = 0;
+        let mut contract_moves = std::collections::BTreeSet::new();
+        for a in &roster.mons {
+            for d in &roster.mons {
+                for lane in 0..a.catalog.len().min(4) {
+                    let c = calc_damage(a, d, lane, Boosts::default());
+                    if !c.damaging() || c.move_name == "Bubble Bop" {
+                        continue;
+                    }
+                    let mut sim = Sim::new(1, vec![build_team_mon(a)], vec![build_team_mon(d)], vec![a.id], vec![d.id], &book);
+                    let bk = sim.battle_key;
+                    let word = a.catalog[lane].word;
+                    let standard = slot_external_accuracy(&mut sim, bk, word).is_some()
+                        && slot_external_volatility(&mut sim, bk, word).is_some()
+                        && slot_external_crit_rate(&mut sim, bk, word).is_some();
+                    if c.custom && (!standard || d.name == "Xmon") {
+                        continue;
+                    }
+                    sim.execute_turn(SWITCH_MOVE_INDEX, 1, 0, SWITCH_MOVE_INDEX, 2, 0);
+                    let ctx = build_damage_calc_context(&mut sim, obs, bk, VOPP, 0, VCPU, 0);
+                    let deltas = [ctx.attackerAttackDelta, ctx.attackerSpAtkDelta, ctx.defenderDefDelta, ctx.defenderSpDefDelta];
+                    if deltas.iter().any(|&x| x != 0) {
+                        continue;
+                    }
+                    let before = mon_current_hp(&mut sim, obs, bk, VCPU, 0);
+                    let (s0, s1) = (0x5eed + lane as u128 + 7 * d.id as u128, 0xbeef + a.id as u128);
+                    sim.execute_turn(lane as u8, s0, 0, NO_OP_INDEX, s1, 0);
+                    let after = mon_current_hp(&mut sim, obs, bk, VCPU, 0);
+                    let mut loss = before - after;
+                    if c.custom && sim.winner_index() == 2 {
+                        sim.execute_turn(NO_OP_INDEX, s0 + 1, 0, NO_OP_INDEX, s1 + 1, 0);
+                        loss -= after - mon_current_hp(&mut sim, obs, bk, VCPU, 0);
+                    }
+                    n += 1;
+                    if c.custom {
+                        contract_moves.insert(c.move_name.clone());
+                    }
+
+                    let h = AttackCalculator::mixRngForAttacker(turn_rng(s0, s1), U256::ZERO);
+                    let (lo, hi) = if c.accuracy < 100 && h.as_limbs()[0] % 100 >= c.accuracy as u64 {
+                        (0, 0)
+                    } else if (h >> 128) % U256::from(100u64) <= U256::from(c.crit_rate) {
+                        (c.crit_min, c.crit_max)
+                    } else {
+                        (c.min, c.max)
+                    };
+                    if !(lo <= loss && loss <= hi) {
+                        bad.push(format!("{} {} -> {}: {loss} not in [{lo},{hi}]", a.name, c.move_name, d.name));
+                    }
+                }
+            }
+        }
+        assert!(n >= 100, "only {n} clean turns");
+        assert!(
+            contract_moves.contains("Infernal Flame") && contract_moves.contains("Wither Away"),
+            "contract moves traced: {contract_moves:?}"
+        );
+        assert!(bad.is_empty(), "{n} turns, {} off:\n{}", bad.len(), bad.join("\n"));
+    }
+}
@@ -0,0 +1,619 @@
+//! Calldata for the battle lifecycle entrypoints, so scripts and bots can hand a wallet
+//! ready-made bytes instead of each re-implementing the ABI:
+//!
+//! - the Engine's built-in dual-signed buffer — `submitTurnMoves` / `submitTurnMovesAndExecute`
+//!   (one packed word plus the revealer's EIP-2098 `r`/`vs`) and the 2-slot
+//!   `submitSlotTurnMoves*` pair — with the EIP-712 digests the revealer signs;
+//! - `SignedMatchmaker.startGame` over a signed `BattleOffer`, with each seat's consent digest;
+//! - LEGACY: the deprecated `DefaultMatchmaker` propose / accept / confirm flow and the
+//!   `DefaultCommitManager` commit / reveal pair, kept for test deployments still wired to them.
+//!
+//! Encoding goes through the `sol!` declarations in [`chomp_bindings`]; this module only bridges
+//! the engine's `chomp_rt` word types to alloy's at the call boundary. [`decode_submission`] runs
+//! the other way, turning a mined transaction's input back into the moves it carried.
+
+use chomp_bindings::alloy_primitives as alloy;
+use chomp_bindings::alloy_sol_types::{SolCall, SolInterface, SolStruct};
+use chomp_bindings::DefaultCommitManager::DefaultCommitManagerCalls;
+use chomp_bindings::IEngine::IEngineCalls;
+use chomp_bindings::SignedCommitManager::SignedCommitManagerCalls;
+use chomp_bindings::{eip712, DefaultCommitManager, DefaultMatchmaker, IEngine, SignedMatchmaker};
+use chomp_engine::Constants::SWITCH_MOVE_INDEX;
+use chomp_rt::{abi_encode_packed, keccak256, Address, Token, B256, U256};
+
+use crate::commit::{move_hash, side_hash, SALT_BITS};
+
+/// `Structs.ProposedBattle`, field for field (interfaces as their addresses).
+#[derive(Clone, Debug, Default)]
+pub struct ProposedBattle {
+    pub p0: Address,
+    pub p0_team_index: u128,
+    pub p0_team_hash: B256,
+    pub p1: Address,
+    pub p1_team_index: u128,
+    pub team_registry: Address,
+    pub rng_oracle: Address,
+    pub ruleset: Address,
+    pub move_manager: Address,
+    pub matchmaker: Address,
+    pub engine_hooks: Vec<Address>,
+}
+
+/// Team indices are `uint96` on-chain.
+pub const TEAM_INDEX_BITS: u16 = 96;
+
+/// A buffered 2-slot side word is 128 bits (`SideWordOverflow` above that), which leaves the
+/// salt 80 of [`crate::sim::pack_side`]'s 104.
+pub const SLOT_SALT_BITS: u32 = 80;
+
+/// `DefaultMatchmaker.FAST_BATTLE_SENTINAL_HASH`: proposing with this team hash skips the
+/// commit-reveal of p0's team — `acceptBattle` starts the battle with the proposed index.
+pub const FAST_BATTLE_SENTINAL_HASH: B256 =
+    B256::new([0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+pub const PROPOSE_BATTLE: &str = DefaultMatchmaker::proposeBattleCall::SIGNATURE;
+pub const ACCEPT_BATTLE: &str = DefaultMatchmaker::acceptBattleCall::SIGNATURE;
+pub const CONFIRM_BATTLE: &str = DefaultMatchmaker::confirmBattleCall::SIGNATURE;
+pub const COMMIT_MOVE: &str = DefaultCommitManager::commitMoveCall::SIGNATURE;
+pub const REVEAL_MOVE: &str = DefaultCommitManager::revealMoveCall::SIGNATURE;
+
+pub fn selector(signature: &str) -> [u8; 4] {
+    let h = keccak256(signature.as_bytes());
+    [h[0], h[1], h[2], h[3]]
+}
+
+pub(crate) fn addr(a: Address) -> alloy::Address {
+    alloy::Address::from_slice(a.as_slice())
+}
+
+pub(crate) fn b256(b: B256) -> alloy::B256 {
+    alloy::B256::from_slice(b.as_slice())
+}
+
+pub(crate) fn word(v: U256) -> alloy::U256 {
+    alloy::U256::from_be_bytes(v.to_be_bytes::<32>())
+}
+
+fn rt_b256(b: alloy::B256) -> B256 {
+    B256::from_slice(b.as_slice())
+}
+
+fn rt_word(v: alloy::U256) -> U256 {
+    U256::from_be_bytes(v.to_be_bytes::<32>())
+}
+
+/// Narrows to an alloy `uint<BITS>`, panicking on overflow: every caller range-checks its inputs
+/// (team indices at `TEAM_INDEX_BITS`, salts at `SALT_BITS`) before encoding.
+pub(crate) fn uint<const BITS: usize, const LIMBS: usize>(v: u128) -> alloy::Uint<BITS, LIMBS> {
+    alloy::Uint::from(v)
+}
+
+impl ProposedBattle {
+    fn to_sol(&self) -> chomp_bindings::ProposedBattle {
+        chomp_bindings::ProposedBattle {
+            p0: addr(self.p0),
+            p0TeamIndex: uint(self.p0_team_index),
+            p0TeamHash: b256(self.p0_team_hash),
+            p1: addr(self.p1),
+            p1TeamIndex: uint(self.p1_team_index),
+            teamRegistry: addr(self.team_registry),
+            rngOracle: addr(self.rng_oracle),
+            ruleset: addr(self.ruleset),
+            moveManager: addr(self.move_manager),
+            matchmaker: addr(self.matchmaker),
+            engineHooks: self.engine_hooks.iter().copied().map(addr).collect(),
+        }
+    }
+}
+
+/// The salted p0 team commitment `confirmBattle` checks:
+/// `keccak256(abi.encodePacked(salt, uint96 p0TeamIndex, uint256[] monRegistryIndices))`, the
+/// indices being `getMonRegistryIndicesForTeam(p0, p0TeamIndex)`.
+pub fn team_hash(salt: B256, p0_team_index: u128, mon_indices: &[U256]) -> B256 {
+    let mut tokens = vec![Token::FixedBytes(salt), Token::Uint(U256::from(p0_team_index), TEAM_INDEX_BITS)];
+    tokens.extend(mon_indices.iter().map(|&i| Token::Uint(i, 256)));
+    keccak256(&abi_encode_packed(&tokens))
+}
+
+/// `DefaultMatchmaker.getBattleProposalIntegrityHash`: the terms p1 accepts, packed with the
+/// hooks padded to a word each (how `encodePacked` lays out an array).
+pub fn integrity_hash(p: &ProposedBattle) -> B256 {
+    let mut tokens = vec![
+        Token::FixedBytes(p.p0_team_hash),
+        Token::Address(p.rng_oracle),
+        Token::Address(p.ruleset),
+        Token::Address(p.team_registry),
+    ];
+    tokens.extend(p.engine_hooks.iter().map(|&h| Token::Uint(U256::from_be_slice(h.as_slice()), 256)));
+    tokens.extend([Token::Address(p.move_manager), Token::Address(p.matchmaker)]);
+    keccak256(&abi_encode_packed(&tokens))
+}
+
+/// LEGACY (`DefaultMatchmaker`). `p1TeamIndex` is not read — the contract overwrites it with its
+/// unset sentinel until p1 accepts.
+pub fn propose_battle(p: &ProposedBattle) -> Vec<u8> {
+    DefaultMatchmaker::proposeBattleCall { proposal: p.to_sol() }.abi_encode()
+}
+
+/// LEGACY (`DefaultMatchmaker`); `battle_integrity_hash` is [`integrity_hash`] of the proposal.
+pub fn accept_battle(battle_key: B256, p1_team_index: u128, battle_integrity_hash: B256) -> Vec<u8> {
+    DefaultMatchmaker::acceptBattleCall {
+        battleKey: b256(battle_key),
+        p1TeamIndex: uint(p1_team_index),
+        battleIntegrityHash: b256(battle_integrity_hash),
+    }
+    .abi_encode()
+}
+
+/// LEGACY (`DefaultMatchmaker`): reveals the salt behind the proposal's [`team_hash`].
+pub fn confirm_battle(battle_key: B256, salt: B256, p0_team_index: u128) -> Vec<u8> {
+    DefaultMatchmaker::confirmBattleCall {
+        battleKey: b256(battle_key),
+        salt: b256(salt),
+        p0TeamIndex: uint(p0_team_index),
+    }
+    .abi_encode()
+}
+
+/// LEGACY (`DefaultCommitManager`).
+pub fn commit_move(battle_key: B256, move_hash: B256) -> Vec<u8> {
+    DefaultCommitManager::commitMoveCall { battleKey: b256(battle_key), moveHash: b256(move_hash) }.abi_encode()
+}
+
+/// LEGACY (`DefaultCommitManager`).
+pub fn reveal_move(battle_key: B256, move_index: u8, salt: u128, extra_data: u16, auto_execute: bool) -> Vec<u8> {
+    DefaultCommitManager::revealMoveCall {
+        battleKey: b256(battle_key),
+        moveIndex: move_index,
+        salt: uint(salt),
+        extraData: extra_data,
+        autoExecute: auto_execute,
+    }
+    .abi_encode()
+}
+
+/// LEGACY (`DefaultCommitManager`). A switch is a reveal of `SWITCH_MOVE_INDEX` with the incoming
+/// team slot as extraData.
+pub fn reveal_switch(battle_key: B256, mon_index: u16, salt: u128, auto_execute: bool) -> Vec<u8> {
+    reveal_move(battle_key, SWITCH_MOVE_INDEX, salt, mon_index, auto_execute)
+}
+
+/// One player's singles move as `submitTurnMoves` packs it: moveIndex in bits 0-7, extraData in
+/// 8-23, salt in 24-127 (a switch is `SWITCH_MOVE_INDEX` with the team slot as extraData).
+pub fn pack_turn_half(move_index: u8, salt: u128, extra_data: u16) -> U256 {
+    U256::from(move_index) | (U256::from(extra_data) << 8) | (U256::from(salt) << 24)
+}
+
+/// Inverse of [`pack_turn_half`]: `(move_index, salt, extra_data)` from the low 128 bits.
+pub fn unpack_turn_half(half: U256) -> (u8, u128, u16) {
+    let field =
+        |shift: usize, bits: u32| ((half >> shift) & ((U256::from(1u8) << bits) - U256::from(1u8))).to::<u128>();
+    (field(0, 8) as u8, field(24, SALT_BITS), field(8, 16) as u16)
+}
+
+/// `submitTurnMoves`' single word: the committer (msg.sender) in the low 128 bits and the
+/// revealer in the high 128, whichever of p0/p1 commits this turn.
+pub fn pack_turn_moves(committer: U256, revealer: U256) -> U256 {
+    committer | (revealer << 128)
+}
+
+/// Splits a 65-byte `r ++ s ++ v` signature, or passes a 64-byte EIP-2098 one through, into the
+/// `(r, vs)` pair the Engine's submit entrypoints take.
+pub fn compact_signature(sig: &[u8]) -> Option<(B256, B256)> {
+    let r = B256::from_slice(sig.get(..32)?);
+    let mut vs: [u8; 32] = sig.get(32..64)?.try_into().ok()?;
+    match sig.len() {
+        64 => {}
+        65 => match sig[64] {
+            27 | 0 => {}
+            28 | 1 => vs[0] |= 0x80,
+            _ => return None,
+        },
+        _ => return None,
+    }
+    Some((r, B256::new(vs)))
+}
+
+/// `submitTurnMovesAndExecute` when `execute` (drain the buffer and run this turn now), else
+/// `submitTurnMoves` (buffer it).
+pub fn submit_turn_moves(battle_key: B256, packed_moves: U256, r: B256, vs: B256, execute: bool) -> Vec<u8> {
+    let (battleKey, packedMoves, r, vs) = (b256(battle_key), word(packed_moves), b256(r), b256(vs));
+    if execute {
+        IEngine::submitTurnMovesAndExecuteCall { battleKey, packedMoves, r, vs }.abi_encode()
+    } else {
+        IEngine::submitTurnMovesCall { battleKey, packedMoves, r, vs }.abi_encode()
+    }
+}
+
+/// 2-slot counterpart of [`submit_turn_moves`]; both side words in [`crate::sim::pack_side`]
+/// layout with at most [`SLOT_SALT_BITS`] of salt.
+pub fn submit_slot_turn_moves(
+    battle_key: B256,
+    committer_side: U256,
+    revealer_side: U256,
+    r: B256,
+    vs: B256,
+    execute: bool,
+) -> Vec<u8> {
+    let (battleKey, committerSidePacked, revealerSidePacked, r, vs) =
+        (b256(battle_key), word(committer_side), word(revealer_side), b256(r), b256(vs));
+    if execute {
+        IEngine::submitSlotTurnMovesAndExecuteCall { battleKey, committerSidePacked, revealerSidePacked, r, vs }
+            .abi_encode()
+    } else {
+        IEngine::submitSlotTurnMovesCall { battleKey, committerSidePacked, revealerSidePacked, r, vs }.abi_encode()
+    }
+}
+
+/// The EIP-712 digest the revealer signs for `submitTurnMoves`. `turn_id` is the next undrained
+/// turn (`turnId + numBuffered`); the domain is the Engine's own.
+pub fn turn_reveal_digest(chain_id: u64, engine: Address, battle_key: B256, turn_id: u64, packed_moves: U256) -> B256 {
+    let field = |shift: usize, bits: u32| {
+        ((packed_moves >> shift) & ((U256::from(1u8) << bits) - U256::from(1u8))).to::<u128>()
+    };
+    let reveal = eip712::DualSignedReveal {
+        battleKey: b256(battle_key),
+        turnId: turn_id,
+        committerMoveHash: b256(move_hash(field(0, 8) as u8, field(24, SALT_BITS), field(8, 16) as u16)),
+        revealerMoveIndex: field(128, 8) as u8,
+        revealerSalt: uint(field(152, SALT_BITS)),
+        revealerExtraData: field(136, 16) as u16,
+    };
+    B256::from_slice(reveal.eip712_signing_hash(&eip712::engine_domain(chain_id, addr(engine))).as_slice())
+}
+
+/// The EIP-712 digest the revealer signs for `submitSlotTurnMoves`.
+pub fn slot_reveal_digest(
+    chain_id: u64,
+    engine: Address,
+    battle_key: B256,
+    turn_id: u64,
+    committer_side: U256,
+    revealer_side: U256,
+) -> B256 {
+    let reveal = eip712::DualSignedSlotReveal {
+        battleKey: b256(battle_key),
+        turnId: turn_id,
+        committerMovesHash: b256(side_hash(committer_side)),
+        revealerSidePacked: word(revealer_side),
+    };
+    B256::from_slice(reveal.eip712_signing_hash(&eip712::engine_domain(chain_id, addr(engine))).as_slice())
+}
+
+/// `Structs.Battle`, field for field (interfaces as their addresses).
+#[derive(Clone, Debug, Default)]
+pub struct Battle {
+    pub p0: Address,
+    pub p0_team_index: u128,
+    pub p1: Address,
+    pub p1_team_index: u128,
+    pub p2: Address,
+    pub p2_team_index: u128,
+    pub p3: Address,
+    pub p3_team_index: u128,
+    pub team_registry: Address,
+    pub rng_oracle: Address,
+    pub ruleset: Address,
+    pub move_manager: Address,
+    pub matchmaker: Address,
+    pub engine_hooks: Vec<Address>,
+}
+
+/// `Structs.BattleOffer`: the battle, the nonce it is pinned to (the engine's pair / party nonce,
+/// or the creator's open-offer nonce when any seat is open) and the `BATTLE_MODE_*`.
+#[derive(Clone, Debug, Default)]
+pub struct BattleOffer {
+    pub battle: Battle,
+    pub pair_hash_nonce: U256,
+    pub battle_mode: u8,
+}
+
+impl BattleOffer {
+    fn to_sol(&self) -> chomp_bindings::BattleOffer {
+        let b = &self.battle;
+        chomp_bindings::BattleOffer {
+            battle: chomp_bindings::Battle {
+                p0: addr(b.p0),
+                p0TeamIndex: uint(b.p0_team_index),
+                p1: addr(b.p1),
+                p1TeamIndex: uint(b.p1_team_index),
+                p2: addr(b.p2),
+                p2TeamIndex: uint(b.p2_team_index),
+                p3: addr(b.p3),
+                p3TeamIndex: uint(b.p3_team_index),
+                teamRegistry: addr(b.team_registry),
+                rngOracle: addr(b.rng_oracle),
+                ruleset: addr(b.ruleset),
+                moveManager: addr(b.move_manager),
+                matchmaker: addr(b.matchmaker),
+                engineHooks: b.engine_hooks.iter().copied().map(addr).collect(),
+            },
+            pairHashNonce: word(self.pair_hash_nonce),
+            battleMode: self.battle_mode,
+        }
+    }
+}
+
+/// `SignedMatchmaker.startGame`. `seat_sigs` is in canonical seat order [p0, p2, p1, p3]; leave a
+/// seat's entry empty for msg.sender and for CPU seats.
+pub fn start_game(offer: &BattleOffer, open_seats_mask: u8, seat_sigs: &[Vec<u8>; 4]) -> Vec<u8> {
+    SignedMatchmaker::startGameCall {
+        offer: offer.to_sol(),
+        openSeatsMask: open_seats_mask,
+        seatSigs: seat_sigs.clone().map(Into::into),
+    }
+    .abi_encode()
+}
+
+/// The digest canonical seat `seat` signs to consent to `offer` under `open_seats_mask`: the
+/// offer's signing form for a named seat, a `SeatFill` over it for an open one.
+pub fn seat_digest(chain_id: u64, offer: &BattleOffer, open_seats_mask: u8, seat: u8) -> B256 {
+    let domain = eip712::signed_matchmaker_domain(chain_id, addr(offer.battle.matchmaker));
+    B256::from_slice(eip712::seat_digest(&domain, &offer.to_sol(), open_seats_mask, seat).as_slice())
+}
+
+/// A battle-lifecycle transaction input, decoded. Move-carrying words keep the layouts their
+/// encoders use: singles halves as [`pack_turn_half`], 2-slot sides as [`crate::sim::pack_side`],
+/// batch entries p0 | p1 halves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Submission {
+    /// `submitTurnMoves` / `submitTurnMovesAndExecute`, or the external
+    /// `SignedCommitManager.executeWithDualSignedMoves` (always executes).
+    Turn { battle_key: B256, committer: U256, revealer: U256, execute: bool },
+    /// `submitSlotTurnMoves` / `submitSlotTurnMovesAndExecute`.
+    SlotTurn { battle_key: B256, committer_side: U256, revealer_side: U256, execute: bool },
+    /// `executeWithMoves` (an external move manager submitting both players).
+    Moves { battle_key: B256, p0: U256, p1: U256 },
+    /// `executeWithSingleMove` / `SignedCommitManager.executeSinglePlayerMove` (a forced switch
+    /// turn: only `playerSwitchForTurnFlag` moves).
+    SingleMove { battle_key: B256, half: U256 },
+    /// `executeWithSlotMoves`.
+    SlotMoves { battle_key: B256, side0: U256, side1: U256 },
+    /// `executeBatchedTurns` (`slots` for `executeBatchedSlotTurns`).
+    Batched { battle_key: B256, entries: Vec<U256>, slots: bool },
+    /// `execute` / `executeBuffered`: runs what is already staged.
+    Execute { battle_key: B256 },
+    /// LEGACY `DefaultCommitManager.commitMove`.
+    Commit { battle_key: B256, move_hash: B256 },
+    /// LEGACY `DefaultCommitManager.revealMove`.
+    Reveal { battle_key: B256, half: U256, auto_execute: bool },
+}
+
+impl Submission {
+    pub fn battle_key(&self) -> B256 {
+        match self {
+            Submission::Turn { battle_key, .. }
+            | Submission::SlotTurn { battle_key, .. }
+            | Submission::Moves { battle_key, .. }
+            | Submission::SingleMove { battle_key, .. }
+            | Submission::SlotMoves { battle_key, .. }
+            | Submission::Batched { battle_key, .. }
+            | Submission::Execute { battle_key }
+            | Submission::Commit { battle_key, .. }
+            | Submission::Reveal { battle_key, .. } => *battle_key,
+        }
+    }
+}
+
+/// Decodes an Engine, `SignedCommitManager` or LEGACY commit manager call; `None` for anything
+/// else (matchmaking, views, unrelated contracts).
+pub fn decode_submission(input: &[u8]) -> Option<Submission> {
+    if let Ok(call) = IEngineCalls::abi_decode(input) {
+        return Some(match call {
+            IEngineCalls::submitTurnMoves(c) => Submission::Turn {
+                battle_key: rt_b256(c.battleKey),
+                committer: rt_word(c.packedMoves) & U256::from(u128::MAX),
+                revealer: rt_word(c.packedMoves) >> 128,
+                execute: false,
+            },
+            IEngineCalls::submitTurnMovesAndExecute(c) => Submission::Turn {
+                battle_key: rt_b256(c.battleKey),
+                committer: rt_word(c.packedMoves) & U256::from(u128::MAX),
+                revealer: rt_word(c.packedMoves) >> 128,
+                execute: true,
+            },
+            IEngineCalls::submitSlotTurnMoves(c) => Submission::SlotTurn {
+                battle_key: rt_b256(c.battleKey),
+                committer_side: rt_word(c.committerSidePacked),
+                revealer_side: rt_word(c.revealerSidePacked),
+                execute: false,
+            },
+            IEngineCalls::submitSlotTurnMovesAndExecute(c) => Submission::SlotTurn {
+                battle_key: rt_b256(c.battleKey),
+                committer_side: rt_word(c.committerSidePacked),
+                revealer_side: rt_word(c.revealerSidePacked),
+                execute: true,
+            },
+            IEngineCalls::executeWithMoves(c) => Submission::Moves {
+                battle_key: rt_b256(c.battleKey),
+                p0: pack_turn_half(c.p0MoveIndex, c.p0Salt.to(), c.p0ExtraData),
+                p1: pack_turn_half(c.p1MoveIndex, c.p1Salt.to(), c.p1ExtraData),
+            },
+            IEngineCalls::executeWithSingleMove(c) => Submission::SingleMove {
+                battle_key: rt_b256(c.battleKey),
+                half: pack_turn_half(c.moveIndex, c.salt.to(), c.extraData),
+            },
+            IEngineCalls::executeWithSlotMoves(c) => Submission::SlotMoves {
+                battle_key: rt_b256(c.battleKey),
+                side0: rt_word(c.side0Packed),
+                side1: rt_word(c.side1Packed),
+            },
+            IEngineCalls::executeBatchedTurns(c) => Submission::Batched {
+                battle_key: rt_b256(c.battleKey),
+                entries: c.entries.into_iter().map(rt_word).collect(),
+                slots: false,
+            },
+            IEngineCalls::executeBatchedSlotTurns(c) => Submission::Batched {
+                battle_key: rt_b256(c.battleKey),
+                entries: c.entries.into_iter().map(rt_word).collect(),
+                slots: true,
+            },
+            IEngineCalls::execute(c) => Submission::Execute { battle_key: rt_b256(c.battleKey) },
+            IEngineCalls::executeBuffered(c) => Submission::Execute { battle_key: rt_b256(c.battleKey) },
+            _ => return None,
+        });
+    }
+    if let Ok(call) = SignedCommitManagerCalls::abi_decode(input) {
+        return match call {
+            SignedCommitManagerCalls::executeWithDualSignedMoves(c) => Some(Submission::Turn {
+                battle_key: rt_b256(c.battleKey),
+                committer: pack_turn_half(c.committerMoveIndex, c.committerSalt.to(), c.committerExtraData),
+                revealer: pack_turn_half(c.revealerMoveIndex, c.revealerSalt.to(), c.revealerExtraData),
+                execute: true,
+            }),
+            SignedCommitManagerCalls::executeSinglePlayerMove(c) => Some(Submission::SingleMove {
+                battle_key: rt_b256(c.battleKey),
+                half: pack_turn_half(c.moveIndex, c.salt.to(), c.extraData),
+            }),
+            _ => None,
+        };
+    }
+    match DefaultCommitManagerCalls::abi_decode(input).ok()? {
+        DefaultCommitManagerCalls::commitMove(c) => {
+            Some(Submission::Commit { battle_key: rt_b256(c.battleKey), move_hash: rt_b256(c.moveHash) })
+        }
+        DefaultCommitManagerCalls::revealMove(c) => Some(Submission::Reveal {
+            battle_key: rt_b256(c.battleKey),
+            half: pack_turn_half(c.moveIndex, c.salt.to(), c.extraData),
+            auto_execute: c.autoExecute,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hand-checked against the raw ABI layout, independently of the bindings that produce it.
+    #[test]
+    fn selectors_and_struct_layout() {
+        assert_eq!(selector("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(
+            PROPOSE_BATTLE,
+            "proposeBattle((address,uint96,bytes32,address,uint96,address,address,address,address,address,address[]))"
+        );
+        assert_eq!(propose_battle(&ProposedBattle::default())[..4], selector(PROPOSE_BATTLE));
+
+        let hooks = vec![Address::repeat_byte(0xaa), Address::repeat_byte(0xbb)];
+        let data = propose_battle(&ProposedBattle { p0: Address::repeat_byte(1), engine_hooks: hooks, ..Default::default() });
+        let w = |i: usize| U256::from_be_slice(&data[4 + 32 * i..4 + 32 * (i + 1)]);
+        assert_eq!(data.len(), 4 + 32 * (1 + 11 + 1 + 2));
+        assert_eq!(w(0), U256::from(32u64));
+        assert_eq!(w(1), U256::from_be_slice(Address::repeat_byte(1).as_slice()));
+        assert_eq!(w(11), U256::from(11 * 32u64));
+        assert_eq!(w(12), U256::from(2u64));
+        assert_eq!(w(14), U256::from_be_slice(Address::repeat_byte(0xbb).as_slice()));
+
+        let sw = reveal_switch(B256::ZERO, 2, 7, true);
+        assert_eq!(sw.len(), 4 + 5 * 32);
+        assert_eq!(sw[4 + 63], SWITCH_MOVE_INDEX);
+        assert_eq!(sw[4 + 127], 2);
+    }
+
+    // Both hashes are encodePacked: narrow fields at their own width, array elements a word each.
+    #[test]
+    fn packed_hashes_match_the_contracts_layout() {
+        let p = ProposedBattle {
+            p0_team_hash: B256::repeat_byte(0x11),
+            rng_oracle: Address::repeat_byte(2),
+            ruleset: Address::repeat_byte(3),
+            team_registry: Address::repeat_byte(4),
+            engine_hooks: vec![Address::repeat_byte(5)],
+            move_manager: Address::repeat_byte(6),
+            matchmaker: Address::repeat_byte(7),
+            ..Default::default()
+        };
+        let mut raw = vec![0x11; 32];
+        for b in [2u8, 3, 4] {
+            raw.extend([b; 20]);
+        }
+        raw.extend([0; 12]);
+        raw.extend([5; 20]);
+        raw.extend([6; 20]);
+        raw.extend([7; 20]);
+        assert_eq!(integrity_hash(&p), keccak256(&raw));
+
+        let mut raw = vec![0x22; 32];
+        raw.extend([0; 11]);
+        raw.push(3);
+        raw.extend(U256::from(9u8).to_be_bytes::<32>());
+        assert_eq!(team_hash(B256::repeat_byte(0x22), 3, &[U256::from(9u8)]), keccak256(&raw));
+    }
+
+    #[test]
+    fn builtin_buffer_word_and_signature() {
+        let word = pack_turn_moves(pack_turn_half(3, 0x1234, 9), pack_turn_half(SWITCH_MOVE_INDEX, 7, 2));
+        assert_eq!(word & U256::from(0xffu8), U256::from(3u8));
+        assert_eq!((word >> 8) & U256::from(0xffffu32), U256::from(9u8));
+        assert_eq!((word >> 24) & U256::from(0xffffu32), U256::from(0x1234u32));
+        assert_eq!((word >> 128) & U256::from(0xffu8), U256::from(SWITCH_MOVE_INDEX));
+        assert_eq!(word >> 152, U256::from(7u8));
+
+        let reveal = eip712::DualSignedReveal {
+            battleKey: b256(B256::repeat_byte(1)),
+            turnId: 4,
+            committerMoveHash: b256(move_hash(3, 0x1234, 9)),
+            revealerMoveIndex: SWITCH_MOVE_INDEX,
+            revealerSalt: uint(7),
+            revealerExtraData: 2,
+        };
+        let engine = Address::repeat_byte(0xee);
+        let want = reveal.eip712_signing_hash(&eip712::engine_domain(31337, addr(engine)));
+        assert_eq!(turn_reveal_digest(31337, engine, B256::repeat_byte(1), 4, word).as_slice(), want.as_slice());
+
+        let mut sig = vec![0xaa; 32];
+        sig.extend([0x01; 32]);
+        sig.push(28);
+        let (r, vs) = compact_signature(&sig).unwrap();
+        assert_eq!(r, B256::repeat_byte(0xaa));
+        assert_eq!(vs[0], 0x81);
+        assert_eq!(compact_signature(&sig[..64]).unwrap().1, B256::repeat_byte(0x01));
+        assert!(compact_signature(&sig[..40]).is_none());
+
+        let buffered = submit_turn_moves(B256::ZERO, word, r, vs, false);
+        assert_eq!(buffered[..4], selector(IEngine::submitTurnMovesCall::SIGNATURE));
+        assert_eq!(U256::from_be_slice(&buffered[36..68]), word);
+    }
+
+    // Every encoder here round-trips through decode_submission.
+    #[test]
+    fn submissions_decode_back_to_their_moves() {
+        let key = B256::repeat_byte(7);
+        let (c, r) = (pack_turn_half(3, 0x1234, 9), pack_turn_half(SWITCH_MOVE_INDEX, (1u128 << SALT_BITS) - 1, 2));
+        assert_eq!(unpack_turn_half(r), (SWITCH_MOVE_INDEX, (1u128 << SALT_BITS) - 1, 2));
+        let input = submit_turn_moves(key, pack_turn_moves(c, r), B256::ZERO, B256::ZERO, true);
+        assert_eq!(
+            decode_submission(&input),
+            Some(Submission::Turn { battle_key: key, committer: c, revealer: r, execute: true })
+        );
+
+        let side = crate::sim::pack_side(1, 0, 2, 0x1000, 5);
+        assert_eq!(crate::sim::unpack_side(side), ([(1, 0), (2, 0x1000)], 5));
+        let input = submit_slot_turn_moves(key, side, U256::ZERO, B256::ZERO, B256::ZERO, false);
+        assert_eq!(
+            decode_submission(&input),
+            Some(Submission::SlotTurn {
+                battle_key: key,
+                committer_side: side,
+                revealer_side: U256::ZERO,
+                execute: false
+            })
+        );
+
+        assert_eq!(
+            decode_submission(&commit_move(key, move_hash(3, 0x1234, 9))),
+            Some(Submission::Commit { battle_key: key, move_hash: move_hash(3, 0x1234, 9) })
+        );
+        assert_eq!(
+            decode_submission(&reveal_switch(key, 2, 0x99, false)),
+            Some(Submission::Reveal {
+                battle_key: key,
+                half: pack_turn_half(SWITCH_MOVE_INDEX, 0x99, 2),
+                auto_execute: false
+            })
+        );
+        assert_eq!(decode_submission(&confirm_battle(key, B256::ZERO, 0)), None);
+        assert_eq!(decode_submission(&[0xde, 0xad]), None);
+    }
+}
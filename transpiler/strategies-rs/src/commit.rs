@@ -0,0 +1,98 @@
+//! Commit-reveal preimages — the move hashes the engine checks a reveal against, computed
+//! off-chain so players and bots can build a commitment up front and verify a reveal later
+//! without a node.
+//!
+//! Singles hash `abi.encodePacked(uint8 moveIndex, uint104 salt, uint16 extraData)` — the one
+//! preimage `Engine._validateAndPackTurn`, `SignedCommitManager` and `DefaultCommitManager` all
+//! share. 2-slot modes hash the committer's whole raw side word
+//! (`keccak256(abi.encodePacked(committerSidePacked))`, [`crate::sim::pack_side`] layout).
+//!
+//! The revealed salts are also the turn's entropy: with no `rngOracle` configured the engine
+//! rolls `keccak256(abi.encode(p0TurnSalt, p1TurnSalt))` ([`turn_rng`]), so a salt must be
+//! unpredictable to the opponent — [`random_salt`] draws one from the OS.
+
+use chomp_rt::{abi_encode, abi_encode_packed, keccak256, Token, B256, U256};
+use std::io::Read;
+
+/// The salt is a `uint104` on-chain; the ABI decoder rejects wider values at every entry point.
+pub const SALT_BITS: u32 = 104;
+
+/// The 16-byte singles preimage: moveIndex (1) ++ salt (13) ++ extraData (2).
+pub fn move_preimage(move_index: u8, salt: u128, extra_data: u16) -> Vec<u8> {
+    abi_encode_packed(&[
+        Token::Uint(U256::from(move_index), 8),
+        Token::Uint(U256::from(salt), SALT_BITS as u16),
+        Token::Uint(U256::from(extra_data), 16),
+    ])
+}
+
+/// Singles move commitment (`committerMoveHash` / `PlayerDecisionData.moveHash`).
+pub fn move_hash(move_index: u8, salt: u128, extra_data: u16) -> B256 {
+    keccak256(&move_preimage(move_index, salt, extra_data))
+}
+
+/// Does the revealed (moveIndex, salt, extraData) open `commitment`? Mirrors the
+/// `WrongPreimage` check.
+pub fn verify_move(commitment: B256, move_index: u8, salt: u128, extra_data: u16) -> bool {
+    move_hash(move_index, salt, extra_data) == commitment
+}
+
+/// 2-slot side commitment (`committerMovesHash`) over a raw `submitSlotTurnMoves` side word.
+pub fn side_hash(side_packed: U256) -> B256 {
+    keccak256(&side_packed.to_be_bytes::<32>())
+}
+
+pub fn verify_side(commitment: B256, side_packed: U256) -> bool {
+    side_hash(side_packed) == commitment
+}
+
+/// A fresh uniformly random `uint104` salt from the OS entropy source.
+pub fn random_salt() -> std::io::Result<u128> {
+    let mut buf = [0u8; 16];
+    std::fs::File::open("/dev/urandom")?.read_exact(&mut buf[3..])?;
+    Ok(u128::from_be_bytes(buf))
+}
+
+/// The inline (no-oracle) turn rng `Engine.execute` derives from both revealed salts.
+pub fn turn_rng(p0_salt: u128, p1_salt: u128) -> U256 {
+    let word = keccak256(&abi_encode(&[
+        Token::Uint(U256::from(p0_salt), SALT_BITS as u16),
+        Token::Uint(U256::from(p1_salt), SALT_BITS as u16),
+    ]));
+    U256::from_be_bytes(word.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn singles_preimage_packs_at_declared_widths() {
+        let pre = move_preimage(0x7d, 0x0102030405060708090a0b0c0d, 0xbeef);
+        assert_eq!(pre.len(), 16);
+        assert_eq!(pre[0], 0x7d);
+        assert_eq!(&pre[1..14], &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13]);
+        assert_eq!(&pre[14..], &[0xbe, 0xef]);
+    }
+
+    #[test]
+    fn reveal_opens_only_its_own_commitment() {
+        let c = move_hash(2, 12345, 1);
+        assert!(verify_move(c, 2, 12345, 1));
+        assert!(!verify_move(c, 2, 12346, 1));
+        assert!(!verify_move(c, 3, 12345, 1));
+        assert!(!verify_move(c, 2, 12345, 0));
+        assert!(verify_side(side_hash(U256::from(0xabcdu64)), U256::from(0xabcdu64)));
+    }
+
+    #[test]
+    fn salts_fit_uint104_and_order_matters_for_rng() {
+        let (a, b) = (random_salt().unwrap(), random_salt().unwrap());
+        assert!(a >> SALT_BITS == 0 && b >> SALT_BITS == 0);
+        assert_ne!(a, b);
+        // keccak256(abi.encode(1, 2))
+        let golden: U256 = "0xe90b7bceb6e7df5418fb78d8ee546e97c83a08bbccc01a0644d599ccd2a7c2e0".parse().unwrap();
+        assert_eq!(turn_rng(1, 2), golden);
+        assert_ne!(turn_rng(1, 2), turn_rng(2, 1));
+    }
+}
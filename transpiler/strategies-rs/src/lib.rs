@@ -22,7 +22,11 @@
 pub mod roster;
 pub mod analysis;
 pub mod arena;
+pub mod battlekey;
 pub mod breadth;
+pub mod calc;
+pub mod calldata;
+pub mod commit;
 pub mod doubles;
 pub mod evaluator;
 pub mod faceoff;
@@ -39,6 +43,8 @@ pub mod override_cpu;
 pub mod search;
 pub mod shared;
 pub mod sim;
+pub mod teamcheck;
 pub mod teams;
+pub mod turnorder;
 pub mod view;
 pub mod yomi;
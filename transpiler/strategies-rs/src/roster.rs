@@ -30,7 +30,7 @@ pub fn address_book() -> std::collections::HashMap<String, Address> {
 
 /// "Bull Rush" / "Hit-And-Dip" → "BullRush" / "HitAndDip": split on space/hyphen, capitalize each
 /// word's first char (rest untouched), join. Mirrors mon-builder.ts:moveNameToContract.
-fn move_name_to_contract(name: &str) -> String {
+pub(crate) fn move_name_to_contract(name: &str) -> String {
     let mut out = String::new();
     for word in name.split(|c: char| c.is_whitespace() || c == '-').filter(|w| !w.is_empty()) {
         let mut chars = word.chars();
@@ -118,6 +118,12 @@ pub fn pack_side(m0: u8, e0: u16, m1: u8, e1: u16, salt: u128) -> U256 {
         | (U256::from(salt) << 48)
 }
 
+/// Inverse of [`pack_side`]: `([(m0, e0), (m1, e1)], salt)`.
+pub fn unpack_side(side: U256) -> ([(u8, u16); 2], u128) {
+    let field = |shift: usize, bits: u32| ((side >> shift) & ((U256::from(1u8) << bits) - U256::from(1u8))).to::<u128>();
+    ([(field(0, 8) as u8, field(8, 16) as u16), (field(24, 8) as u8, field(32, 16) as u16)], field(48, 104))
+}
+
 impl Sim {
     /// Stand up a SINGLES battle world. `book` maps contract names to addresses
     /// (the arena's exported address book); when empty, no contracts are
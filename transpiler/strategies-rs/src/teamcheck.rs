@@ -0,0 +1,203 @@
+//! Team legality — the registry's `createTeam` (PackedTeamStore) and `assignMoves` (MonExp) rules
+//! evaluated off-chain against the roster (`drool/*.csv`, the data the registry is seeded from).
+//! Every violation is reported, each tagged with the custom error the contract would revert with
+//! (or a descriptive tag for checks with no contract error of their own), instead of only the
+//! first revert a registration tx would hit.
+//!
+//! Move selections are catalog-lane sets, exactly as `assignMoves` takes them: lanes
+//! [0, MOVES_PER_MON) are the default loadout (level 0), higher lanes unlock at
+//! `FIRST_UNLOCK_LEVEL` (MonExp's uniform `_unlockLevelForLane` curve, not moves.csv's
+//! per-move UnlockLevel column).
+
+use serde::Deserialize;
+
+use crate::roster::{addr_of, addr_to_word, move_name_to_contract, Roster, RosterMon};
+
+/// `GAME_MONS_PER_TEAM` / `GAME_MOVES_PER_MON` (Constants.sol).
+pub const MONS_PER_TEAM: usize = 4;
+pub const MOVES_PER_MON: usize = 4;
+/// MonExp.FIRST_UNLOCK_LEVEL.
+pub const FIRST_UNLOCK_LEVEL: u32 = 6;
+/// PackedTeamStore.ONES_MASK — mon ids pack at 8 bits.
+const MAX_MON_ID: u32 = 0xFF;
+/// The selection field is an 8-bit lane bitmap.
+const MAX_LANES: usize = 8;
+
+/// A mon or move given by registry index or by name (case-insensitive).
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum Ref {
+    Index(u32),
+    Name(String),
+}
+
+impl std::fmt::Display for Ref {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ref::Index(i) => write!(f, "#{i}"),
+            Ref::Name(n) => write!(f, "{n:?}"),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ProposedMon {
+    pub mon: Ref,
+    /// Catalog lanes (or move names) for the battle set; omitted = the default loadout.
+    #[serde(default)]
+    pub moves: Option<Vec<Ref>>,
+    /// The player's level for this mon (gates lanes ≥ MOVES_PER_MON).
+    #[serde(default)]
+    pub level: u32,
+    /// Optional ability name. Abilities aren't selectable on-chain (one per mon), so this only
+    /// catches a proposal that misdescribes the mon.
+    #[serde(default)]
+    pub ability: Option<String>,
+}
+
+/// One team entry: a bare mon ref (`cpu-teams.json` rows) or a full spec.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum Entry {
+    Bare(Ref),
+    Full(ProposedMon),
+}
+
+impl Entry {
+    pub fn spec(&self) -> ProposedMon {
+        match self {
+            Entry::Bare(r) => ProposedMon { mon: r.clone(), moves: None, level: 0, ability: None },
+            Entry::Full(p) => p.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ProposedTeam {
+    pub mons: Vec<Entry>,
+}
+
+#[derive(Debug)]
+pub struct Violation {
+    /// Team position the violation is about (None = whole-team rule).
+    pub slot: Option<usize>,
+    /// The contract's revert for this rule (a descriptive tag for the off-chain-only unknown-mon
+    /// and ability checks).
+    pub error: &'static str,
+    pub detail: String,
+}
+
+fn unlock_level_for_lane(lane: usize) -> u32 {
+    if lane < MOVES_PER_MON {
+        0
+    } else {
+        FIRST_UNLOCK_LEVEL
+    }
+}
+
+fn resolve_mon<'a>(roster: &'a Roster, r: &Ref) -> Option<&'a RosterMon> {
+    match r {
+        Ref::Index(id) => roster.mon_by_id(*id),
+        Ref::Name(n) => roster.mons.iter().find(|m| m.name.eq_ignore_ascii_case(n)),
+    }
+}
+
+fn resolve_lane(m: &RosterMon, r: &Ref) -> Option<usize> {
+    match r {
+        Ref::Index(l) => Some(*l as usize),
+        Ref::Name(n) => m.catalog.iter().position(|c| c.name.eq_ignore_ascii_case(n)),
+    }
+}
+
+/// All rule violations for `team` (empty = the registry would accept it).
+pub fn check_team(roster: &Roster, team: &ProposedTeam) -> Vec<Violation> {
+    let mut out = Vec::new();
+    let mut push = |slot: Option<usize>, error: &'static str, detail: String| out.push(Violation { slot, error, detail });
+
+    if team.mons.len() != MONS_PER_TEAM {
+        push(None, "InvalidTeamSize", format!("{} mons, the registry takes exactly {MONS_PER_TEAM}", team.mons.len()));
+    }
+
+    let mut seen: Vec<(u32, usize)> = Vec::new();
+    for (slot, entry) in team.mons.iter().enumerate() {
+        let p = entry.spec();
+        if let Ref::Index(id) = p.mon {
+            if id > MAX_MON_ID {
+                push(Some(slot), "MonIdTooLarge", format!("mon id {id} does not fit the 8-bit team lane"));
+                continue;
+            }
+        }
+        let Some(m) = resolve_mon(roster, &p.mon) else {
+            // On-chain an unregistered id fails ownership (`NotOwner`) before any team rule runs.
+            push(Some(slot), "UnknownMon", format!("{} is not in the roster", p.mon));
+            continue;
+        };
+        if let Some(&(_, first)) = seen.iter().find(|(id, _)| *id == m.id) {
+            push(Some(slot), "DuplicateMonId", format!("{} already at slot {first}", m.name));
+        }
+        seen.push((m.id, slot));
+
+        if let Some(a) = &p.ability {
+            let want = addr_to_word(addr_of(&move_name_to_contract(a)));
+            if want != m.ability {
+                push(Some(slot), "AbilityMismatch", format!("{} does not have ability {a:?}", m.name));
+            }
+        }
+
+        let Some(moves) = &p.moves else { continue };
+        if moves.is_empty() {
+            push(Some(slot), "EmptyMoveSelection", format!("{}: no moves selected", m.name));
+            continue;
+        }
+        let mut bitmap = 0u8;
+        for mv in moves {
+            let lane = match resolve_lane(m, mv) {
+                Some(l) if l < m.catalog.len() && l < MAX_LANES => l,
+                _ => {
+                    push(Some(slot), "InvalidMoveLane", format!("{}: {mv} is not in its catalog", m.name));
+                    continue;
+                }
+            };
+            let need = unlock_level_for_lane(lane);
+            if need > p.level {
+                push(
+                    Some(slot),
+                    "MoveNotUnlocked",
+                    format!("{}: {} (lane {lane}) unlocks at level {need}, mon is level {}", m.name, m.catalog[lane].name, p.level),
+                );
+            }
+            bitmap |= 1 << lane;
+        }
+        if bitmap.count_ones() as usize > MOVES_PER_MON {
+            push(
+                Some(slot),
+                "TooManyMovesSelected",
+                format!("{}: {} distinct moves, at most {MOVES_PER_MON}", m.name, bitmap.count_ones()),
+            );
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roster::load_roster;
+
+    fn team(json: &str) -> ProposedTeam {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn flags_each_broken_rule_once() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("..");
+        let roster = load_roster(&root);
+        assert!(check_team(&roster, &team(r#"{"mons": [0, 3, 10, 5]}"#)).is_empty());
+        let named = r#"{"mons": [{"mon": "ghouliath", "ability": "rise from the grave"}, 3, 10, 5]}"#;
+        assert!(check_team(&roster, &team(named)).is_empty());
+
+        let v = check_team(&roster, &team(r#"{"mons": [0, 0, {"mon": 10, "moves": [0, 1, 2, 3, 4]}]}"#));
+        let errors: Vec<&str> = v.iter().map(|v| v.error).collect();
+        assert_eq!(errors, ["InvalidTeamSize", "DuplicateMonId", "MoveNotUnlocked", "TooManyMovesSelected"]);
+    }
+}
@@ -0,0 +1,198 @@
+//! Turn order — who acts first for a roster matchup and a pair of chosen actions, answered by the
+//! transpiled `Engine.computePriorityPlayerIndex` itself on a fresh 1v1 rather than a re-derivation.
+//!
+//! The engine's rule, in order: higher move priority (switch / no-op = `SWITCH_PRIORITY`), then
+//! higher current speed (base + delta), then `rng % 2`. The engine is asked under both rng
+//! parities — when the answers differ the turn is a pure speed tie and [`TurnOrder::first`] is
+//! None; [`TurnOrder::resolve`] settles it for a concrete turn rng ([`crate::commit::turn_rng`]).
+
+use chomp_engine::moves::MoveSlotLib;
+use chomp_engine::Constants::{SWITCH_MOVE_INDEX, SWITCH_PRIORITY};
+use chomp_engine::Engine;
+use chomp_rt::U256;
+
+use crate::arena::build_team_mon;
+use crate::roster::{self, RosterMon};
+use crate::sim::{pack_turn, Sim};
+
+/// One side's action this turn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Catalog lane (lanes past the default loadout are slotted in as move 0).
+    Lane(usize),
+    Switch,
+}
+
+#[derive(Clone, Copy)]
+pub struct Side<'a> {
+    pub mon: &'a RosterMon,
+    pub action: Action,
+    /// Speed boost as % of base speed (the delta one StatBoosts application writes).
+    pub speed_pct: i32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TurnOrder {
+    pub priority: [u32; 2],
+    pub speed: [u32; 2],
+    /// Player index that acts first, None = speed tie settled by the turn rng.
+    pub first: Option<usize>,
+}
+
+impl TurnOrder {
+    /// What decided the order: "priority", "speed" or "rng".
+    pub fn decided_by(&self) -> &'static str {
+        if self.priority[0] != self.priority[1] {
+            "priority"
+        } else if self.first.is_some() {
+            "speed"
+        } else {
+            "rng"
+        }
+    }
+
+    /// First mover for a concrete turn rng (the engine's `rng % 2` tiebreak).
+    pub fn resolve(&self, rng: U256) -> usize {
+        self.first.unwrap_or(if rng.bit(0) { 1 } else { 0 })
+    }
+}
+
+/// The side's battle mon (speed boost folded into base speed — the engine only ever reads
+/// `speed + speedDelta`) and the move index it submits.
+fn battle_mon(side: &Side) -> (chomp_engine::Structs::Mon, u8) {
+    let mut mon = build_team_mon(side.mon);
+    let speed = mon.stats.speed as i64;
+    mon.stats.speed = (speed + speed * side.speed_pct as i64 / 100).max(0) as u32;
+    let move_index = match side.action {
+        Action::Switch => SWITCH_MOVE_INDEX,
+        Action::Lane(l) if l < mon.moves.len() => l as u8,
+        Action::Lane(l) => {
+            mon.moves[0] = side.mon.catalog[l].word;
+            0
+        }
+    };
+    (mon, move_index)
+}
+
+pub fn turn_order(p0: &Side, p1: &Side) -> TurnOrder {
+    let book = roster::address_book();
+    let ((m0, mi0), (m1, mi1)) = (battle_mon(p0), battle_mon(p1));
+    let speed = [m0.stats.speed, m1.stats.speed];
+    let words = [m0.moves[mi0.min(3) as usize], m1.moves[mi1.min(3) as usize]];
+    let mut sim = Sim::new(1, vec![m0], vec![m1], vec![p0.mon.id], vec![p1.mon.id], &book);
+    let bk = sim.battle_key;
+
+    // A switch's target is irrelevant to ordering, so the raw index 0 is submitted as-is. Both
+    // submissions are live before any read — dynamic-priority moves inspect the opponent's choice.
+    sim.world.reset_transient();
+    sim.world.Engine._turnP0Packed = pack_turn(mi0, 0, 0);
+    sim.world.Engine._turnP1Packed = pack_turn(mi1, 0, 0);
+    let mut priority = [0u32; 2];
+    for (p, (mi, word)) in [(mi0, words[0]), (mi1, words[1])].into_iter().enumerate() {
+        priority[p] = if mi == SWITCH_MOVE_INDEX {
+            SWITCH_PRIORITY.to::<u32>()
+        } else {
+            MoveSlotLib::priority(&mut sim.world, word, sim.engine_addr, bk, U256::from(p as u64))
+        };
+    }
+    let even = Engine::computePriorityPlayerIndex(&mut sim.world, bk, U256::ZERO);
+    let odd = Engine::computePriorityPlayerIndex(&mut sim.world, bk, U256::from(1u64));
+    sim.world.reset_transient();
+
+    TurnOrder { priority, speed, first: (even == odd).then(|| even.to::<usize>()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::{calc_damage, Boosts};
+    use crate::commit::turn_rng;
+    use crate::roster::load_roster;
+    use crate::view::{mon_current_hp, Seat, VCPU, VOPP};
+
+    // Engine trace: every ordered pair plays a real turn at 1 HP a side, each team backed by a
+    // second copy of its lead. Both sides use their first move that always hits for damage, so
+    // whoever acts first KOs the other before it moves and the lead left standing is the observed
+    // first mover. A switch against such an attack shows the same way: the attack lands on the
+    // incoming mon when the switch went first, on the lead otherwise. Speed ties are held to the
+    // turn's actual rng.
+    #[test]
+    fn engine_order_follows_priority_then_speed() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join("..");
+        let roster = load_roster(&root);
+        let book = roster::address_book();
+        let obs = Seat { cpu: 1 };
+        let vp = [VOPP, VCPU];
+        let sure_hit = |a: &RosterMon, d| {
+            (0..a.catalog.len().min(4)).find(|&lane| {
+                let c = calc_damage(a, d, lane, Boosts::default());
+                c.damaging() && c.min > 0 && c.accuracy >= 100
+            })
+        };
+        let (mut moves, mut switches) = (0, 0);
+        let mut decided_by = std::collections::BTreeSet::new();
+        let mut bad = Vec::new();
+        for a in &roster.mons {
+            for d in &roster.mons {
+                let (Some(lane), hit) = (sure_hit(a, d), sure_hit(d, a)) else { continue };
+                let mut sides = [a, d].map(|mon| Side { mon, action: Action::Lane(lane), speed_pct: 0 });
+                for (p1_action, name) in [(hit.map(Action::Lane), "move"), (Some(Action::Switch), "switch")] {
+                    let Some(p1_action) = p1_action else { continue };
+                    sides[1].action = p1_action;
+                    let o = turn_order(&sides[0], &sides[1]);
+                    let team = |side: &Side| {
+                        let mut mon = battle_mon(side).0;
+                        mon.stats.hp = 1;
+                        vec![mon.clone(), mon]
+                    };
+                    let mut sim = Sim::new(2, team(&sides[0]), team(&sides[1]), vec![a.id; 2], vec![d.id; 2], &book);
+                    let bk = sim.battle_key;
+                    sim.execute_turn(SWITCH_MOVE_INDEX, 1, 0, SWITCH_MOVE_INDEX, 2, 0);
+                    if (0..2).any(|p| mon_current_hp(&mut sim, obs, bk, vp[p], 0) < 1) {
+                        continue; // a switch-in ability (Volthare's) already struck
+                    }
+                    let (s0, s1) = (0x5eed + d.id as u128, 0xbeef + a.id as u128);
+                    let mi = |side: &Side| battle_mon(side).1;
+                    let (mi1, extra1) = if p1_action == Action::Switch { (SWITCH_MOVE_INDEX, 1) } else { (mi(&sides[1]), 0) };
+                    sim.execute_turn(mi(&sides[0]), s0, 0, mi1, s1, extra1);
+                    let mut hp = |p: usize, mon| mon_current_hp(&mut sim, obs, bk, vp[p], mon);
+                    let observed = if p1_action != Action::Switch {
+                        match (hp(0, 0) > 0, hp(1, 0) > 0) {
+                            (true, false) => Some(0),
+                            (false, true) => Some(1),
+                            _ => None,
+                        }
+                    } else {
+                        match (hp(1, 0) > 0, hp(1, 1) > 0) {
+                            (false, true) => Some(0),
+                            (true, false) => Some(1),
+                            _ => None,
+                        }
+                    };
+                    let Some(observed) = observed else {
+                        bad.push(format!("{} vs {} ({name}): no single mon fell", a.name, d.name));
+                        continue;
+                    };
+                    if p1_action == Action::Switch {
+                        switches += 1;
+                    } else {
+                        moves += 1;
+                    }
+                    decided_by.insert(o.decided_by());
+                    let want = o.resolve(turn_rng(s0, s1));
+                    if observed != want {
+                        bad.push(format!(
+                            "{} vs {} ({name}): engine moved p{observed} first, tool says p{want} ({})",
+                            a.name,
+                            d.name,
+                            o.decided_by()
+                        ));
+                    }
+                }
+            }
+        }
+        assert!(moves >= 100 && switches >= 100, "only {moves} move turns, {switches} switch turns");
+        assert!(decided_by.contains("priority") && decided_by.contains("speed"), "decided by: {decided_by:?}");
+        assert!(bad.is_empty(), "{} mismatches:\n{}", bad.len(), bad.join("\n"));
+    }
+}
@@ -158,9 +158,12 @@ pub fn move_slot(sim: &mut Sim, seat: Seat, bk: B256, vp: u8, mon: usize, mi: us
 }
 
 /// Damage context between the two ACTIVE mons (proxied: both player-index
-/// args seat-mapped).
+/// args seat-mapped; the mon-index args are each side's physical active).
 pub fn damage_calc_context(sim: &mut Sim, seat: Seat, bk: B256, atk_vp: u8, def_vp: u8) -> DamageCalcContext {
-    Engine::getDamageCalcContext(&mut sim.world, bk, seat.phys(atk_vp), seat.phys(def_vp))
+    let active = Engine::getActiveMonIndexForBattleState(&mut sim.world, bk);
+    let (atk, def) = (seat.phys(atk_vp), seat.phys(def_vp));
+    let (atk_mon, def_mon) = (active[chomp_rt::usize(atk)], active[chomp_rt::usize(def)]);
+    Engine::getDamageCalcContext(&mut sim.world, bk, atk, atk_mon, def, def_mon)
 }
 
 /// `TYPE_CALC.getTypeEffectiveness` — TypeCalculator delegates straight to
@@ -214,6 +217,18 @@ pub fn slot_external_accuracy(sim: &mut Sim, bk: B256, slot: U256) -> Option<u32
     chomp_engine::dispatch::try_accuracy(&mut sim.world, target, bk)
 }
 
+/// External move's `volatility(battleKey)` — duck-typed probe, None when absent.
+pub fn slot_external_volatility(sim: &mut Sim, bk: B256, slot: U256) -> Option<u32> {
+    let target = MoveSlotLib::toIMoveSet(slot);
+    chomp_engine::dispatch::try_volatility(&mut sim.world, target, bk)
+}
+
+/// External move's `critRate(battleKey)` — duck-typed probe, None when absent.
+pub fn slot_external_crit_rate(sim: &mut Sim, bk: B256, slot: U256) -> Option<u32> {
+    let target = MoveSlotLib::toIMoveSet(slot);
+    chomp_engine::dispatch::try_critRate(&mut sim.world, target, bk)
+}
+
 /// Virtual-side hypothetical turn: fork + silent execute with the seat's
 /// p0/p1 mapped to physical sides (the transposed `__runHypotheticalFork`
 /// arg swap). Returns the fork key.
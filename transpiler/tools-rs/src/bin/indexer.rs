@@ -0,0 +1,213 @@
+//! Engine indexer — follows the Engine's events and maintains a SQLite database of battles
+//! (seats, mode, start / end, winner, turn count) and effect usage (how many battles applied each
+//! effect, and how often), then prints a summary:
+//!   cargo run --release -p chomp-tools --bin indexer -- --rpc https://… [--db chomp-index.sqlite] \
+//!       [--from-block N] [--to-block N] [--chunk 10000] [--follow [--poll 12]] [--no-effects] \
+//!       [--network MAINNET] [--engine 0x…] [--deployments path/to/deployments.json]
+//!   cargo run --release -p chomp-tools --bin indexer -- --db chomp-index.sqlite --stats
+//! Indexing resumes after the database's cursor unless `--from-block` says otherwise. Effects are
+//! counted from `getBattle` diffs at each block that executed a battle (the engine emits no effect
+//! events), so a historical backfill needs an archive node; `--no-effects` skips those reads.
+
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types_eth::Filter;
+use chomp_bindings::alloy_sol_types::SolEventInterface;
+use chomp_bindings::IEngine::{self, IEngineEvents};
+use chomp_tools::deployments::{self, Deployments};
+use chomp_tools::index::{Index, Update};
+use chomp_tools::rpc::{Resolver, Result, Rpc, LOG_CHUNK};
+use chomp_tools::timeline::{Change, Snapshot};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: indexer --rpc <url> [--db <file>] [--from-block N] [--to-block N] [--chunk N] \
+                     [--follow [--poll SECS]] [--no-effects] [--network MAINNET|TESTNET] [--engine 0x…] \
+                     [--deployments <file>] | indexer [--db <file>] --stats";
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn has(args: &[String], flag: &str) -> bool {
+    args.iter().any(|a| a == flag)
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("indexer: {msg}\n{USAGE}");
+    std::process::exit(2);
+}
+
+fn arg_n(args: &[String], flag: &str) -> Option<u64> {
+    arg(args, flag).map(|v| v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not a number: {v}"))))
+}
+
+/// What one block did to one battle.
+#[derive(Default)]
+struct Touch {
+    started: bool,
+    executed: bool,
+    winner: Option<Address>,
+}
+
+struct Indexer<'a> {
+    rpc: &'a Rpc,
+    engine: Address,
+    resolver: Resolver,
+    effects: bool,
+    /// Last read state of each live battle, so a block costs one `getBattle` rather than two.
+    snapshots: HashMap<B256, Snapshot>,
+    named: HashSet<Address>,
+}
+
+impl Indexer<'_> {
+    async fn snapshot(&self, key: B256, block: u64) -> Result<Snapshot> {
+        let r = self.rpc.call(self.engine, IEngine::getBattleCall { battleKey: key }, Some(block)).await?;
+        Ok(Snapshot::from_view(&r.config, &r.data))
+    }
+
+    async fn range(&mut self, index: &Index, from: u64, to: u64) -> Result<Vec<Update>> {
+        let logs = self.rpc.logs(Filter::new().address(self.engine), from, to, to - from + 1).await?;
+        let mut updates = Vec::new();
+        let mut touched: BTreeMap<(u64, B256), Touch> = BTreeMap::new();
+        for log in &logs {
+            let (Some(block), Some(tx)) = (log.block_number, log.transaction_hash) else { continue };
+            let Ok(event) = IEngineEvents::decode_log(&log.inner) else { continue };
+            let (key, touch) = match event.data {
+                IEngineEvents::BattleStart(e) => {
+                    let players = vec![e.p0, e.p1];
+                    updates.push(Update::Start { battle_key: e.battleKey, mode: 0, players, block, tx });
+                    (e.battleKey, Touch { started: true, ..Default::default() })
+                }
+                IEngineEvents::SlotBattleStart(e) => {
+                    let players = vec![e.p0, e.p1, e.p2, e.p3];
+                    updates.push(Update::Start { battle_key: e.battleKey, mode: e.battleMode, players, block, tx });
+                    (e.battleKey, Touch { started: true, ..Default::default() })
+                }
+                IEngineEvents::EngineExecute(e) => (e.battleKey, Touch { executed: true, ..Default::default() }),
+                IEngineEvents::BattleComplete(e) => {
+                    (e.battleKey, Touch { winner: Some(e.winner), ..Default::default() })
+                }
+                IEngineEvents::BattleCompleteWithBatchTurns(e) => {
+                    let winner = e.payload.get(..20).map(Address::from_slice);
+                    (e.battleKey, Touch { executed: true, winner, ..Default::default() })
+                }
+                IEngineEvents::BattleCompleteWithBatchSlotTurns(e) => {
+                    let winner = e.payload.get(..20).map(Address::from_slice);
+                    (e.battleKey, Touch { executed: true, winner, ..Default::default() })
+                }
+                _ => continue,
+            };
+            let t = touched.entry((block, key)).or_default();
+            t.started |= touch.started;
+            t.executed |= touch.executed;
+            t.winner = t.winner.or(touch.winner);
+        }
+
+        for ((block, key), t) in touched {
+            let mut turns = None;
+            if self.effects && (t.executed || t.winner.is_some()) {
+                let before = match self.snapshots.remove(&key) {
+                    Some(s) => s,
+                    // Started this block: everything present afterwards was applied this block.
+                    None if t.started => Snapshot::default(),
+                    None => self.snapshot(key, block - 1).await?,
+                };
+                let after = self.snapshot(key, block).await?;
+                let mut counts: BTreeMap<Address, u64> = BTreeMap::new();
+                for c in before.diff(&after) {
+                    if let Change::EffectAdded { effect, .. } = c {
+                        *counts.entry(effect).or_default() += 1;
+                    }
+                }
+                for (effect, count) in counts {
+                    if self.named.insert(effect) && !index.has_effect_name(effect)? {
+                        let name = self.resolver.effect(self.rpc, effect).await;
+                        updates.push(Update::EffectName { effect, name });
+                    }
+                    updates.push(Update::Effect { battle_key: key, effect, count });
+                }
+                turns = Some(after.turn_id as u64);
+                if t.winner.is_none() {
+                    self.snapshots.insert(key, after);
+                }
+            }
+            if let Some(winner) = t.winner {
+                updates.push(Update::Complete { battle_key: key, winner, block, turns });
+            }
+        }
+        Ok(updates)
+    }
+}
+
+fn print_stats(index: &Index) -> rusqlite::Result<()> {
+    let (started, completed) = index.battle_counts()?;
+    println!("battles: {started} indexed, {completed} completed");
+    let usage = index.effect_usage()?;
+    if !usage.is_empty() {
+        println!("{:<44} {:>8} {:>12}  name", "effect", "battles", "applications");
+    }
+    for u in usage {
+        println!("{:<44} {:>8} {:>12}  {}", u.effect, u.battles, u.applications, u.name.unwrap_or_default());
+    }
+    Ok(())
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args).await {
+        eprintln!("indexer: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: &[String]) -> Result<()> {
+    let db = PathBuf::from(arg(args, "--db").unwrap_or_else(|| "chomp-index.sqlite".to_string()));
+    let mut index = Index::open(&db)?;
+    if has(args, "--stats") {
+        return Ok(print_stats(&index)?);
+    }
+
+    let url = arg(args, "--rpc").unwrap_or_else(|| fail("missing --rpc"));
+    let network = arg(args, "--network").unwrap_or_else(|| "MAINNET".to_string());
+    let path = arg(args, "--deployments").map(Into::into).unwrap_or_else(deployments::default_path);
+    let record = Deployments::load(&path, &network).unwrap_or_else(|e| fail(&e));
+    let engine = match arg(args, "--engine") {
+        Some(v) => v.parse().unwrap_or_else(|_| fail(&format!("--engine: not an address: {v}"))),
+        None => record.address("ENGINE").unwrap_or_else(|| fail(&format!("no ENGINE in {network}; pass --engine"))),
+    };
+    let chunk = arg_n(args, "--chunk").unwrap_or(LOG_CHUNK).max(1);
+    let poll = std::time::Duration::from_secs(arg_n(args, "--poll").unwrap_or(12));
+    let follow = has(args, "--follow");
+
+    let rpc = Rpc::connect(&url)?;
+    let mut ix = Indexer {
+        rpc: &rpc,
+        engine,
+        resolver: Resolver::new(record),
+        effects: !has(args, "--no-effects"),
+        snapshots: HashMap::new(),
+        named: HashSet::new(),
+    };
+    let mut from = match arg_n(args, "--from-block") {
+        Some(n) => n,
+        None => index.cursor()?.map_or(0, |c| c + 1),
+    };
+    loop {
+        let head = rpc.head().await?;
+        let to = arg_n(args, "--to-block").map_or(head, |n| n.min(head));
+        while from <= to {
+            let end = to.min(from + chunk - 1);
+            let updates = ix.range(&index, from, end).await?;
+            index.apply(end, &updates)?;
+            eprintln!("indexer: blocks {from}..={end}: {} updates", updates.len());
+            from = end + 1;
+        }
+        if !follow {
+            break;
+        }
+        tokio::time::sleep(poll).await;
+    }
+    print_stats(&index)?;
+    Ok(())
+}
@@ -0,0 +1,228 @@
+//! Battle log decoder — fetches the engine's events for one battle over RPC and prints a
+//! turn-by-turn timeline: who used which move (named from the team's move slots), switches,
+//! damage and stat changes, KOs, and effects applied / removed (named from `deployments.json`,
+//! falling back to the contract's `name()`):
+//!   cargo run --release -p chomp-tools --bin log-decoder -- --rpc https://… --key 0x… \
+//!       [--from-block 0] [--to-block <head>] [--chunk 10000] [--network MAINNET] [--engine 0x…] \
+//!       [--deployments path/to/deployments.json]
+//! The engine emits no damage or effect events, so outcomes are the `getBattle` diff across each
+//! block that touched the battle — reads pinned to past blocks need an archive node.
+
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types_eth::Filter;
+use chomp_bindings::alloy_sol_types::SolEventInterface;
+use chomp_bindings::IEngine::{self, IEngineEvents};
+use chomp_tools::deployments::{self, Deployments};
+use chomp_tools::rpc::{Resolver, Result, Rpc, LOG_CHUNK};
+use chomp_tools::timeline::{
+    decode_batch_payload, decode_batch_slot_payload, decode_mon_moves, decode_side_word, decode_turn_word, Action,
+    Change, Decision, Snapshot,
+};
+use std::collections::BTreeMap;
+
+const USAGE: &str = "usage: log-decoder --rpc <url> --key 0x… [--from-block N] [--to-block N] [--chunk N] \
+                     [--network MAINNET|TESTNET] [--engine 0x…] [--deployments <file>]";
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("log-decoder: {msg}\n{USAGE}");
+    std::process::exit(2);
+}
+
+fn arg_n(args: &[String], flag: &str) -> Option<u64> {
+    arg(args, flag).map(|v| v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not a number: {v}"))))
+}
+
+async fn snapshot(rpc: &Rpc, engine: Address, key: B256, block: u64) -> Result<Snapshot> {
+    let r = rpc.call(engine, IEngine::getBattleCall { battleKey: key }, Some(block)).await?;
+    Ok(Snapshot::from_view(&r.config, &r.data))
+}
+
+/// Reads the block before and after `block`; a battle that starts in `block` has no "before".
+struct Step {
+    before: Option<Snapshot>,
+    after: Snapshot,
+}
+
+struct Printer<'a> {
+    rpc: &'a Rpc,
+    resolver: Resolver,
+}
+
+impl Printer<'_> {
+    async fn effect(&mut self, addr: Address) -> String {
+        self.resolver.effect(self.rpc, addr).await
+    }
+
+    async fn action(&mut self, snap: &Snapshot, side: usize, mon: usize, d: Decision) -> String {
+        match d.action() {
+            Action::Move(i) => {
+                let name = match snap.moves[side].get(mon).and_then(|m| m.get(i as usize)) {
+                    Some(&word) => self.resolver.move_slot(self.rpc, word).await,
+                    None => format!("move {i}"),
+                };
+                let extra = if d.extra_data != 0 { format!(" (extra {})", d.extra_data) } else { String::new() };
+                format!("p{side} mon {mon} uses {name}{extra}")
+            }
+            Action::Switch(to) => format!("p{side} switches mon {mon} -> mon {to}"),
+            Action::NoOp => format!("p{side} mon {mon} rests"),
+        }
+    }
+
+    async fn changes(&mut self, before: &Snapshot, after: &Snapshot) {
+        for c in before.diff(after) {
+            let line = match c {
+                Change::ActiveSwitched { side, slot, from, to } => {
+                    format!("p{side} slot {slot}: mon {from} -> mon {to}")
+                }
+                Change::Stat { side, mon, stat, before, after } => {
+                    format!("p{side} mon {mon} {stat} {:+} (now {after:+})", after - before)
+                }
+                Change::KnockedOut { side, mon } => format!("p{side} mon {mon} is knocked out"),
+                Change::EffectAdded { side: Some(side), mon, effect } => {
+                    format!("p{side} mon {mon} + {}", self.effect(effect).await)
+                }
+                Change::EffectRemoved { side: Some(side), mon, effect } => {
+                    format!("p{side} mon {mon} - {}", self.effect(effect).await)
+                }
+                Change::EffectAdded { side: None, effect, .. } => format!("global + {}", self.effect(effect).await),
+                Change::EffectRemoved { side: None, effect, .. } => format!("global - {}", self.effect(effect).await),
+            };
+            println!("    {line}");
+        }
+    }
+
+    /// A run of buffered turns replayed from a completion payload: the active mon is tracked
+    /// through the switches in the payload itself, since no snapshot exists between sub-turns.
+    async fn batch(&mut self, snap: &Snapshot, turns: Vec<[Decision; 2]>) {
+        let mut active = [snap.active[0][0], snap.active[1][0]];
+        for (i, turn) in turns.into_iter().enumerate() {
+            println!("  buffered turn {}", snap.turn_id as usize + i);
+            for (side, d) in turn.into_iter().enumerate() {
+                println!("    {}", self.action(snap, side, active[side], d).await);
+                if let Action::Switch(to) = d.action() {
+                    active[side] = to as usize;
+                }
+            }
+        }
+    }
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args).await {
+        eprintln!("log-decoder: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: &[String]) -> Result<()> {
+    let url = arg(args, "--rpc").unwrap_or_else(|| fail("missing --rpc"));
+    let key_s = arg(args, "--key").unwrap_or_else(|| fail("missing --key"));
+    let key: B256 = key_s.parse().unwrap_or_else(|_| fail(&format!("--key: not a bytes32: {key_s}")));
+    let network = arg(args, "--network").unwrap_or_else(|| "MAINNET".to_string());
+    let path = arg(args, "--deployments").map(Into::into).unwrap_or_else(deployments::default_path);
+    let record = Deployments::load(&path, &network).unwrap_or_else(|e| fail(&e));
+    let engine = match arg(args, "--engine") {
+        Some(v) => v.parse().unwrap_or_else(|_| fail(&format!("--engine: not an address: {v}"))),
+        None => record.address("ENGINE").unwrap_or_else(|| fail(&format!("no ENGINE in {network}; pass --engine"))),
+    };
+
+    let rpc = Rpc::connect(&url)?;
+    let from = arg_n(args, "--from-block").unwrap_or(0);
+    let to = match arg_n(args, "--to-block") {
+        Some(n) => n,
+        None => rpc.head().await?,
+    };
+    let chunk = arg_n(args, "--chunk").unwrap_or(LOG_CHUNK);
+    let logs = rpc.logs(Filter::new().address(engine).topic1(key), from, to, chunk).await?;
+
+    let mut blocks: BTreeMap<u64, Vec<(Option<B256>, IEngineEvents)>> = BTreeMap::new();
+    for log in &logs {
+        let (Some(block), Ok(decoded)) = (log.block_number, IEngineEvents::decode_log(&log.inner)) else {
+            continue;
+        };
+        blocks.entry(block).or_default().push((log.transaction_hash, decoded.data));
+    }
+    if blocks.is_empty() {
+        println!("no engine events for {key} at {engine} in blocks {from}..={to}");
+        return Ok(());
+    }
+
+    let mut p = Printer { rpc: &rpc, resolver: Resolver::new(record) };
+    println!("battle {key} on {network} engine {engine}");
+    for (&block, events) in &blocks {
+        let starts =
+            events.iter().any(|(_, e)| matches!(e, IEngineEvents::BattleStart(_) | IEngineEvents::SlotBattleStart(_)));
+        let step = Step {
+            before: if starts { None } else { Some(snapshot(&rpc, engine, key, block - 1).await?) },
+            after: snapshot(&rpc, engine, key, block).await?,
+        };
+        // Move slots and actives are read pre-block; a start block only has the post-state.
+        let pre = step.before.as_ref().unwrap_or(&step.after);
+        println!("block {block}  turn {}", pre.turn_id);
+        for (tx, event) in events {
+            let tx = tx.map(|h| format!("  [tx {h}]")).unwrap_or_default();
+            match event {
+                IEngineEvents::BattleStart(e) => println!("  start: p0 {} vs p1 {}{tx}", e.p0, e.p1),
+                IEngineEvents::SlotBattleStart(e) => println!(
+                    "  start (mode {}): p0 {} + p2 {} vs p1 {} + p3 {}{tx}",
+                    e.battleMode, e.p0, e.p2, e.p1, e.p3
+                ),
+                IEngineEvents::MonMoves(e) => {
+                    println!("  moves{tx}");
+                    for (side, lane) in decode_mon_moves(e.packedMoves, e.packedSalts).into_iter().enumerate() {
+                        if let Some((mon, d)) = lane {
+                            println!("    {}", p.action(pre, side, mon as usize, d).await);
+                        }
+                    }
+                }
+                IEngineEvents::MovesSubmitted(e) => {
+                    println!("  moves submitted{tx}");
+                    let word = U256::from_be_bytes(e.packed.0);
+                    for (side, d) in decode_turn_word(word).into_iter().enumerate() {
+                        println!("    {}", p.action(pre, side, pre.active[side][0], d).await);
+                    }
+                }
+                IEngineEvents::SlotMovesSubmitted(e) => {
+                    println!("  slot moves submitted{tx}");
+                    for (side, word) in [e.side0Packed, e.side1Packed].into_iter().enumerate() {
+                        for (slot, (move_index, extra_data)) in decode_side_word(word).slots.into_iter().enumerate() {
+                            let mon = pre.active[side].get(slot).copied().unwrap_or(slot);
+                            let d = Decision { move_index, extra_data, salt: 0 };
+                            println!("    slot {slot}: {}", p.action(pre, side, mon, d).await);
+                        }
+                    }
+                }
+                IEngineEvents::EngineExecute(_) => println!("  execute{tx}"),
+                IEngineEvents::BattleComplete(e) => println!("  complete: winner {}{tx}", e.winner),
+                IEngineEvents::BattleCompleteWithBatchTurns(e) => match decode_batch_payload(&e.payload) {
+                    Some((winner, turns)) => {
+                        println!("  complete (batched): winner {winner}{tx}");
+                        p.batch(pre, turns).await;
+                    }
+                    None => println!("  complete (batched): malformed payload {}{tx}", e.payload),
+                },
+                IEngineEvents::BattleCompleteWithBatchSlotTurns(e) => match decode_batch_slot_payload(&e.payload) {
+                    Some((winner, turns)) => {
+                        println!("  complete (batched slots): winner {winner}, {} turns{tx}", turns.len());
+                        for (i, sides) in turns.iter().enumerate() {
+                            let lanes: Vec<String> =
+                                sides.iter().map(|s| format!("{:?}", s.slots.map(|(m, e)| (m, e)))).collect();
+                            println!("    turn {}: {}", pre.turn_id as usize + i, lanes.join(" | "));
+                        }
+                    }
+                    None => println!("  complete (batched slots): malformed payload {}{tx}", e.payload),
+                },
+            }
+        }
+        if let Some(before) = &step.before {
+            p.changes(before, &step.after).await;
+        }
+    }
+    Ok(())
+}
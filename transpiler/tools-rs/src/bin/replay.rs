@@ -0,0 +1,145 @@
+//! Battle replay — pulls every transaction that touched a battle key (found through the battle's
+//! indexed logs on the Engine and its move managers), decodes the move calldata, and prints the
+//! reconstructed turn-by-turn battle as JSON:
+//!   cargo run --release -p chomp-tools --bin replay -- --rpc https://… --key 0x… [--from-block 0] \
+//!       [--to-block <head>] [--chunk 10000] [--network MAINNET] [--engine 0x…] [--managers 0x…,0x…] \
+//!       [--deployments path/to/deployments.json] [--out replay.json]
+//! `--managers` adds move-manager contracts whose transactions carry moves (the LEGACY
+//! DefaultCommitManager, a SignedCommitManager); the record's COMMIT_MANAGER is always included.
+//! The range must cover the battle's start block — the seats come from its `BattleStart`.
+
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types_eth::Filter;
+use chomp_bindings::alloy_sol_types::SolEventInterface;
+use chomp_bindings::IEngine::{self, IEngineEvents};
+use chomp_strategies::calldata::{decode_submission, Submission};
+use chomp_tools::deployments::{self, Deployments};
+use chomp_tools::replay::{reconstruct, MinedTx};
+use chomp_tools::rpc::{Result, Rpc, LOG_CHUNK};
+
+const USAGE: &str = "usage: replay --rpc <url> --key 0x… [--from-block N] [--to-block N] [--chunk N] \
+                     [--network MAINNET|TESTNET] [--engine 0x…] [--managers 0x…,0x…] [--deployments <file>] \
+                     [--out <file>]";
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("replay: {msg}\n{USAGE}");
+    std::process::exit(2);
+}
+
+fn arg_n(args: &[String], flag: &str) -> Option<u64> {
+    arg(args, flag).map(|v| v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not a number: {v}"))))
+}
+
+fn parse_addr(flag: &str, v: &str) -> Address {
+    v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not an address: {v}")))
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args).await {
+        eprintln!("replay: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: &[String]) -> Result<()> {
+    let url = arg(args, "--rpc").unwrap_or_else(|| fail("missing --rpc"));
+    let key_s = arg(args, "--key").unwrap_or_else(|| fail("missing --key"));
+    let key: B256 = key_s.parse().unwrap_or_else(|_| fail(&format!("--key: not a bytes32: {key_s}")));
+    let network = arg(args, "--network").unwrap_or_else(|| "MAINNET".to_string());
+    let path = arg(args, "--deployments").map(Into::into).unwrap_or_else(deployments::default_path);
+    let record = Deployments::load(&path, &network).unwrap_or_else(|e| fail(&e));
+    let engine = match arg(args, "--engine") {
+        Some(v) => parse_addr("--engine", &v),
+        None => record.address("ENGINE").unwrap_or_else(|| fail(&format!("no ENGINE in {network}; pass --engine"))),
+    };
+    let mut contracts = vec![engine];
+    contracts.extend(record.address("COMMIT_MANAGER"));
+    if let Some(list) = arg(args, "--managers") {
+        contracts.extend(list.split(',').filter(|s| !s.is_empty()).map(|s| parse_addr("--managers", s)));
+    }
+
+    let rpc = Rpc::connect(&url)?;
+    let from = arg_n(args, "--from-block").unwrap_or(0);
+    let to = match arg_n(args, "--to-block") {
+        Some(n) => n,
+        None => rpc.head().await?,
+    };
+    let chunk = arg_n(args, "--chunk").unwrap_or(LOG_CHUNK);
+    let logs = rpc.logs(Filter::new().address(contracts).topic1(key), from, to, chunk).await?;
+
+    // Transactions in mined order, each flagged if it ran the engine for this battle.
+    let mut order: Vec<(B256, u64, bool)> = Vec::new();
+    let (mut players, mut slots, mut winner) = (Vec::new(), false, None);
+    for log in &logs {
+        let (Some(hash), Some(block)) = (log.transaction_hash, log.block_number) else { continue };
+        if order.last().map(|o| o.0) != Some(hash) {
+            order.push((hash, block, false));
+        }
+        if log.address() != engine {
+            continue;
+        }
+        let Ok(event) = IEngineEvents::decode_log(&log.inner) else { continue };
+        let executed = match event.data {
+            IEngineEvents::BattleStart(e) => {
+                players = vec![e.p0, e.p1];
+                false
+            }
+            IEngineEvents::SlotBattleStart(e) => {
+                players = vec![e.p0, e.p1, e.p2, e.p3];
+                slots = true;
+                false
+            }
+            IEngineEvents::BattleComplete(e) => {
+                winner = Some(e.winner);
+                true
+            }
+            IEngineEvents::BattleCompleteWithBatchTurns(e) => {
+                winner = e.payload.get(..20).map(Address::from_slice);
+                true
+            }
+            IEngineEvents::BattleCompleteWithBatchSlotTurns(e) => {
+                winner = e.payload.get(..20).map(Address::from_slice);
+                true
+            }
+            IEngineEvents::EngineExecute(_) => true,
+            _ => false,
+        };
+        if executed {
+            order.last_mut().unwrap().2 = true;
+        }
+    }
+    if players.is_empty() {
+        return Err(format!("no BattleStart for {key} in blocks {from}..={to}; widen --from-block").into());
+    }
+
+    let mut txs = Vec::with_capacity(order.len());
+    for (hash, block, executed) in order {
+        let (sender, input) = rpc.transaction(hash).await?;
+        // A move manager's single-player turn does not say whose it is; the engine's switch flag does.
+        let forced_side = match decode_submission(&input) {
+            Some(Submission::SingleMove { .. }) if !players.contains(&sender) => {
+                let r = rpc.call(engine, IEngine::getBattleCall { battleKey: key }, Some(block - 1)).await?;
+                Some(r.data.playerSwitchForTurnFlag as usize).filter(|&s| s < 2)
+            }
+            _ => None,
+        };
+        txs.push(MinedTx { hash, block, from: sender, input: input.to_vec(), executed, forced_side });
+    }
+
+    let replay = reconstruct(key, &players, slots, &txs, winner);
+    let json = serde_json::to_string_pretty(&replay)?;
+    match arg(args, "--out") {
+        Some(out) => {
+            std::fs::write(&out, json + "\n")?;
+            eprintln!("replay: {} turns -> {out}", replay.turns.len());
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
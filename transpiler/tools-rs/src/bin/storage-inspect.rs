@@ -0,0 +1,199 @@
+//! Storage inspector — reads the Engine's raw storage (`eth_getStorageAt`) through its Foundry
+//! storage layout and pretty-prints a battle: turn, winner, each side's team with the active mon
+//! marked, stat boosts (non-zero `MonState` deltas), KOs, and per-mon and global effects named
+//! from `deployments.json` (falling back to the effect's `name()`):
+//!   forge inspect src/Engine.sol:Engine storageLayout --json > engine-layout.json
+//!   cargo run --release -p chomp-tools --bin storage-inspect -- --rpc https://… --layout engine-layout.json \
+//!       --key 0x… [--block N] [--network MAINNET] [--engine 0x…] [--deployments path/to/deployments.json]
+//! Any other state variable path decodes to JSON instead of the battle view:
+//!   cargo run --release -p chomp-tools --bin storage-inspect -- --rpc https://… --layout engine-layout.json \
+//!       --var 'battleConfig[0x…].p0States[1]' [--contract 0x…] [--block N]
+//! Battle storage is recycled once a battle ends, so past battles need `--block` inside their
+//! lifetime (and an archive node).
+
+use alloy_primitives::{Address, B256, U256};
+use chomp_bindings::IEngine;
+use chomp_engine::Constants::{
+    CLEARED_MON_STATE_SENTINEL, EFFECT_SLOTS_PER_MON, PLAYER_EFFECT_BITS, TOMBSTONE_ADDRESS,
+};
+use chomp_tools::deployments::{self, Deployments};
+use chomp_tools::rpc::{Resolver, Result, Rpc};
+use chomp_tools::storage::{Layout, Storage};
+use serde_json::Value;
+use std::collections::HashMap;
+
+const USAGE: &str = "usage: storage-inspect --rpc <url> --layout <file> (--key 0x… | --var <path>) [--block N] \
+                     [--network MAINNET|TESTNET] [--engine 0x…] [--contract 0x…] [--deployments <file>]";
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("storage-inspect: {msg}\n{USAGE}");
+    std::process::exit(2);
+}
+
+/// `eth_getStorageAt` behind the layout decoder's synchronous [`Storage`] trait, cached per slot.
+struct RpcStorage<'a> {
+    rpc: &'a Rpc,
+    contract: Address,
+    block: Option<u64>,
+    cache: HashMap<U256, U256>,
+}
+
+impl Storage for RpcStorage<'_> {
+    fn load(&mut self, slot: U256) -> std::result::Result<U256, String> {
+        if let Some(v) = self.cache.get(&slot) {
+            return Ok(*v);
+        }
+        let handle = tokio::runtime::Handle::current();
+        let word =
+            tokio::task::block_in_place(|| handle.block_on(self.rpc.storage_at(self.contract, slot, self.block)))
+                .map_err(|e| e.to_string())?;
+        let v = U256::from_be_bytes(word.0);
+        self.cache.insert(slot, v);
+        Ok(v)
+    }
+}
+
+fn read(layout: &Layout, st: &mut RpcStorage, path: &str) -> Result<Value> {
+    let loc = layout.locate(path, st)?;
+    Ok(layout.decode(&loc, st)?)
+}
+
+fn num(v: &Value) -> i64 {
+    v.as_str().and_then(|s| s.parse().ok()).unwrap_or(0)
+}
+
+async fn battle(layout: &Layout, st: &mut RpcStorage<'_>, resolver: &mut Resolver, key: B256) -> Result<()> {
+    let rpc = st.rpc;
+    let storage_key = rpc.call(st.contract, IEngine::getStorageKeyCall { battleKey: key }, st.block).await?;
+    let data = read(layout, st, &format!("battleData[{key}]"))?;
+    let config = |field: &str| format!("battleConfig[{storage_key}].{field}");
+    let team_sizes = num(&read(layout, st, &config("teamSizes"))?);
+    let counts: [U256; 2] = [
+        read(layout, st, &config("packedP0EffectsCount"))?.as_str().unwrap_or("0").parse()?,
+        read(layout, st, &config("packedP1EffectsCount"))?.as_str().unwrap_or("0").parse()?,
+    ];
+    let tomb = Address::from_slice(TOMBSTONE_ADDRESS.as_slice());
+
+    let at = st.block.map_or("latest".to_string(), |b| format!("block {b}"));
+    println!("battle {key} (storage key {storage_key}) at {at}");
+    let winner = match num(&data["winnerIndex"]) {
+        2 => "none yet".to_string(),
+        w => format!("p{w}"),
+    };
+    let flag = match num(&data["playerSwitchForTurnFlag"]) {
+        2 => "both act".to_string(),
+        f if f < 2 => format!("p{f} switches"),
+        f => format!("slots {:#06b} act", f & 0xf),
+    };
+    println!("turn {}, winner {winner}, {flag}", num(&data["turnId"]));
+
+    let two_slot = data["isTwoSlotMode"] == Value::Bool(true);
+    for side in 0..2usize {
+        let lane = |field: &str| (num(&data[field]) >> (8 * side)) & 0xff;
+        let mut active = vec![lane("activeMonIndex")];
+        if two_slot {
+            active.push(lane("activeMonExt"));
+        }
+        let player = data[if side == 0 { "p0" } else { "p1" }].as_str().unwrap_or("?").to_string();
+        let size = (team_sizes >> (4 * side)) & 0xf;
+        println!("p{side} {player}: {size} mons");
+        for mon in 0..size {
+            let state = read(layout, st, &config(&format!("p{side}States[{mon}]")))?;
+            let mut deltas = Vec::new();
+            for (field, short) in [
+                ("hpDelta", "hp"),
+                ("staminaDelta", "stamina"),
+                ("speedDelta", "speed"),
+                ("attackDelta", "attack"),
+                ("defenceDelta", "defence"),
+                ("specialAttackDelta", "sp.attack"),
+                ("specialDefenceDelta", "sp.defence"),
+            ] {
+                let d = num(&state[field]);
+                if d != 0 && d != CLEARED_MON_STATE_SENTINEL as i64 {
+                    deltas.push(format!("{short} {d:+}"));
+                }
+            }
+            if state["isKnockedOut"] == Value::Bool(true) {
+                deltas.push("KO".to_string());
+            }
+            if state["shouldSkipTurn"] == Value::Bool(true) {
+                deltas.push("skips turn".to_string());
+            }
+            let mark = if active.contains(&mon) { "*" } else { " " };
+            let shown = if deltas.is_empty() { "unchanged".to_string() } else { deltas.join(", ") };
+            println!("  {mark} mon {mon}: {shown}");
+
+            let bits = PLAYER_EFFECT_BITS as usize;
+            let count = ((counts[side] >> (mon as usize * bits)) & U256::from((1u64 << bits) - 1)).to::<u64>();
+            let stride = EFFECT_SLOTS_PER_MON.to::<u64>();
+            let mut names = Vec::new();
+            for j in 0..count {
+                let slot = stride * mon as u64 + j;
+                let effect = read(layout, st, &config(&format!("p{side}Effects[{slot}].effect")))?;
+                let addr: Address = effect.as_str().unwrap_or_default().parse()?;
+                if addr != tomb && !addr.is_zero() {
+                    names.push(resolver.effect(rpc, addr).await);
+                }
+            }
+            if !names.is_empty() {
+                println!("      effects: {}", names.join(", "));
+            }
+        }
+    }
+    let globals = num(&read(layout, st, &config("globalEffectsLength"))?);
+    let mut names = Vec::new();
+    for j in 0..globals {
+        let effect = read(layout, st, &config(&format!("globalEffects[{j}].effect")))?;
+        let addr: Address = effect.as_str().unwrap_or_default().parse()?;
+        if addr != tomb && !addr.is_zero() {
+            names.push(resolver.effect(rpc, addr).await);
+        }
+    }
+    println!("global effects: {}", if names.is_empty() { "none".to_string() } else { names.join(", ") });
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args).await {
+        eprintln!("storage-inspect: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: &[String]) -> Result<()> {
+    let url = arg(args, "--rpc").unwrap_or_else(|| fail("missing --rpc"));
+    let layout_path = arg(args, "--layout").unwrap_or_else(|| fail("missing --layout"));
+    let text = std::fs::read_to_string(&layout_path).unwrap_or_else(|e| fail(&format!("{layout_path}: {e}")));
+    let layout = Layout::parse(&text).unwrap_or_else(|e| fail(&format!("{layout_path}: {e}")));
+    let network = arg(args, "--network").unwrap_or_else(|| "MAINNET".to_string());
+    let path = arg(args, "--deployments").map(Into::into).unwrap_or_else(deployments::default_path);
+    let record = Deployments::load(&path, &network).unwrap_or_else(|e| fail(&e));
+    let parse_addr = |flag: &str, v: String| -> Address {
+        v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not an address: {v}")))
+    };
+    let engine = match arg(args, "--engine") {
+        Some(v) => parse_addr("--engine", v),
+        None => record.address("ENGINE").unwrap_or_else(|| fail(&format!("no ENGINE in {network}; pass --engine"))),
+    };
+    let block =
+        arg(args, "--block").map(|v| v.parse().unwrap_or_else(|_| fail(&format!("--block: not a number: {v}"))));
+
+    let rpc = Rpc::connect(&url)?;
+    if let Some(var) = arg(args, "--var") {
+        let contract = arg(args, "--contract").map_or(engine, |v| parse_addr("--contract", v));
+        let mut st = RpcStorage { rpc: &rpc, contract, block, cache: HashMap::new() };
+        println!("{}", serde_json::to_string_pretty(&read(&layout, &mut st, &var)?)?);
+        return Ok(());
+    }
+    let key_s = arg(args, "--key").unwrap_or_else(|| fail("missing --key (or --var)"));
+    let key: B256 = key_s.parse().unwrap_or_else(|_| fail(&format!("--key: not a bytes32: {key_s}")));
+    let mut st = RpcStorage { rpc: &rpc, contract: engine, block, cache: HashMap::new() };
+    battle(&layout, &mut st, &mut Resolver::new(record), key).await
+}
@@ -0,0 +1,131 @@
+//! Team registry exporter — reads every live team of the given players (default: every player
+//! that ever rolled, from the registry's `Roll` events) and writes them as JSON or CSV with mon,
+//! ability and move names resolved:
+//!   cargo run --release -p chomp-tools --bin team-export -- --rpc https://… [--players 0x…,0x…] \
+//!       [--from-block 0] [--to-block <head>] [--chunk 10000] [--block N] [--format json|csv] \
+//!       [--out teams.json] [--network MAINNET] [--registry 0x…] [--deployments path/to/deployments.json]
+//! Teams are read through `getTeams`, so stats carry the player's facets and moves are the
+//! selected battle loadout, exactly what a battle would load. Mon names come from drool/mons.csv
+//! under CHOMP_ROOT; `--format` defaults to the `--out` extension, else JSON.
+
+use alloy_primitives::Address;
+use alloy_rpc_types_eth::Filter;
+use chomp_bindings::alloy_sol_types::SolEvent;
+use chomp_bindings::ITeamRegistry;
+use chomp_strategies::roster::load_roster;
+use chomp_tools::deployments::{self, Deployments};
+use chomp_tools::rpc::{Resolver, Result, Rpc, LOG_CHUNK};
+use chomp_tools::teams::{to_csv, Stats, Team, TeamMon};
+use std::collections::BTreeSet;
+
+const USAGE: &str = "usage: team-export --rpc <url> [--players 0x…,0x…] [--from-block N] [--to-block N] \
+                     [--chunk N] [--block N] [--format json|csv] [--out <file>] [--network MAINNET|TESTNET] \
+                     [--registry 0x…] [--deployments <file>]";
+
+fn arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter().position(|a| a == flag).and_then(|i| args.get(i + 1)).cloned()
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("team-export: {msg}\n{USAGE}");
+    std::process::exit(2);
+}
+
+fn arg_n(args: &[String], flag: &str) -> Option<u64> {
+    arg(args, flag).map(|v| v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not a number: {v}"))))
+}
+
+fn parse_addr(flag: &str, v: &str) -> Address {
+    v.parse().unwrap_or_else(|_| fail(&format!("{flag}: not an address: {v}")))
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args).await {
+        eprintln!("team-export: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: &[String]) -> Result<()> {
+    let url = arg(args, "--rpc").unwrap_or_else(|| fail("missing --rpc"));
+    let network = arg(args, "--network").unwrap_or_else(|| "MAINNET".to_string());
+    let path = arg(args, "--deployments").map(Into::into).unwrap_or_else(deployments::default_path);
+    let record = Deployments::load(&path, &network).unwrap_or_else(|e| fail(&e));
+    let registry = match arg(args, "--registry") {
+        Some(v) => parse_addr("--registry", &v),
+        None => record
+            .address("GACHA_TEAM_REGISTRY")
+            .unwrap_or_else(|| fail(&format!("no GACHA_TEAM_REGISTRY in {network}; pass --registry"))),
+    };
+    let out = arg(args, "--out");
+    let format = arg(args, "--format")
+        .or_else(|| out.as_deref().and_then(|o| o.rsplit_once('.')).map(|(_, ext)| ext.to_string()))
+        .unwrap_or_else(|| "json".to_string());
+    if format != "json" && format != "csv" {
+        fail(&format!("--format: want json or csv, got {format}"));
+    }
+
+    let rpc = Rpc::connect(&url)?;
+    let block = arg_n(args, "--block");
+    let mut players = BTreeSet::new();
+    match arg(args, "--players") {
+        Some(list) => players.extend(list.split(',').filter(|s| !s.is_empty()).map(|s| parse_addr("--players", s))),
+        None => {
+            let from = arg_n(args, "--from-block").unwrap_or(0);
+            let to = match arg_n(args, "--to-block").or(block) {
+                Some(n) => n,
+                None => rpc.head().await?,
+            };
+            let chunk = arg_n(args, "--chunk").unwrap_or(LOG_CHUNK);
+            let filter = Filter::new().address(registry).event_signature(ITeamRegistry::Roll::SIGNATURE_HASH);
+            for log in rpc.logs(filter, from, to, chunk).await? {
+                if let Ok(roll) = ITeamRegistry::Roll::decode_log(&log.inner) {
+                    players.insert(roll.data.player);
+                }
+            }
+            eprintln!("team-export: {} players rolled in blocks {from}..={to}", players.len());
+        }
+    }
+
+    let roster = load_roster(&deployments::chomp_root());
+    let mut resolver = Resolver::new(record);
+    let mut teams = Vec::new();
+    for player in players {
+        let live = rpc.call(registry, ITeamRegistry::getPlayerTeamsCall { player }, block).await?;
+        for (slot, ids) in live.slots.iter().zip(&live.teamMonIds) {
+            let call = ITeamRegistry::getTeamsCall { p0: player, p0TeamIndex: *slot, p1: player, p1TeamIndex: *slot };
+            let team = rpc.call(registry, call, block).await?._0;
+            let mut mons = Vec::with_capacity(team.len());
+            for (mon, id) in team.iter().zip(ids) {
+                let mon_id = id.saturating_to::<u64>();
+                let mut moves = Vec::with_capacity(mon.moves.len());
+                for word in &mon.moves {
+                    moves.push(resolver.move_slot(&rpc, *word).await);
+                }
+                mons.push(TeamMon {
+                    mon_id,
+                    name: roster.mon_name(mon_id as u32),
+                    ability: resolver.ability(&rpc, mon.ability).await,
+                    moves,
+                    stats: Stats::from(&mon.stats),
+                });
+            }
+            teams.push(Team { player, slot: slot.saturating_to::<u64>(), mons });
+        }
+    }
+
+    let text = match format.as_str() {
+        "csv" => to_csv(&teams),
+        _ => serde_json::to_string_pretty(&teams)? + "\n",
+    };
+    match out {
+        Some(out) => {
+            std::fs::write(&out, text)?;
+            eprintln!("team-export: {} teams -> {out}", teams.len());
+        }
+        None => print!("{text}"),
+    }
+    Ok(())
+}
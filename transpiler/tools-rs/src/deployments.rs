@@ -0,0 +1,133 @@
+//! Name resolution over `deployments.json`, the canonical per-network address record
+//! `processing/` writes after each deploy.
+//!
+//! Values come in three shapes: a bare 20-byte address (contracts, deployed moves / effects), a
+//! packed inline-move word (the exact word a `Mon.moves` slot holds), and a packed inline ability
+//! (`type_id << 248 | address`). Slot words are matched exactly first; failing that, a word that
+//! is not an inline move is named by the address in its low 160 bits (a deployed move carries
+//! its static metadata above the address, `MoveSlotLib.packDeployed`).
+
+use alloy_primitives::{Address, U256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// `Constants.MOVE_META_TAG`.
+const MOVE_META_TAG_BIT: usize = 160;
+
+#[derive(Clone, Debug, Default)]
+pub struct Deployments {
+    pub network: String,
+    raw: BTreeMap<String, U256>,
+    by_address: HashMap<Address, String>,
+    by_word: HashMap<U256, String>,
+}
+
+fn low_address(word: U256) -> Address {
+    Address::from_word(word.to_be_bytes::<32>().into())
+}
+
+/// `type_id << 248 | address`: a top byte and nothing else above the address.
+fn is_packed_ability(word: U256) -> bool {
+    let above = word >> 160;
+    above >> 88 != U256::ZERO && above & ((U256::from(1u8) << 88) - U256::from(1u8)) == U256::ZERO
+}
+
+/// `MoveSlotLib.isInline`: untagged with data above the address.
+pub fn is_inline_move(word: U256) -> bool {
+    !word.bit(MOVE_META_TAG_BIT) && word >> 160 != U256::ZERO
+}
+
+impl Deployments {
+    pub fn load(path: &Path, network: &str) -> Result<Deployments, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        Deployments::parse(&text, network).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn parse(text: &str, network: &str) -> Result<Deployments, String> {
+        let doc: serde_json::Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        let entries = doc
+            .get(network)
+            .and_then(|n| n.as_object())
+            .ok_or_else(|| format!("no {network} section (want MAINNET or TESTNET)"))?;
+        let mut d = Deployments { network: network.to_string(), ..Default::default() };
+        for (name, v) in entries {
+            let s = v.as_str().ok_or_else(|| format!("{name}: not a string"))?;
+            let word: U256 = s.parse().map_err(|_| format!("{name}: not a hex word: {s}"))?;
+            d.raw.insert(name.clone(), word);
+            if s.len() == 42 {
+                d.by_address.insert(low_address(word), name.clone());
+            } else {
+                d.by_word.insert(word, name.clone());
+            }
+        }
+        // Packed abilities expose their contract only inside the word: name it when nothing
+        // deployed bare already claims that address.
+        for (&word, name) in &d.by_word {
+            if is_packed_ability(word) {
+                d.by_address.entry(low_address(word)).or_insert_with(|| name.clone());
+            }
+        }
+        Ok(d)
+    }
+
+    /// A deployed contract by record name (`ENGINE`, `GACHA_TEAM_REGISTRY`, …).
+    pub fn address(&self, name: &str) -> Option<Address> {
+        let word = *self.raw.get(name)?;
+        (word >> 160 == U256::ZERO).then(|| low_address(word))
+    }
+
+    pub fn name_of(&self, addr: Address) -> Option<&str> {
+        self.by_address.get(&addr).map(String::as_str)
+    }
+
+    /// A `Mon.moves` / ability slot word.
+    pub fn name_of_word(&self, word: U256) -> Option<&str> {
+        if let Some(n) = self.by_word.get(&word) {
+            return Some(n);
+        }
+        if is_inline_move(word) {
+            return None;
+        }
+        self.name_of(low_address(word))
+    }
+}
+
+/// `CHOMP_ROOT` or the repo root this crate is synced under.
+pub fn chomp_root() -> std::path::PathBuf {
+    std::env::var("CHOMP_ROOT")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join("..").join(".."))
+}
+
+/// `deployments.json` under [`chomp_root`].
+pub fn default_path() -> std::path::PathBuf {
+    chomp_root().join("deployments.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_every_value_shape() {
+        let d = Deployments::load(&default_path(), "MAINNET").unwrap();
+        let engine = d.address("ENGINE").expect("ENGINE deployed");
+        assert_eq!(d.name_of(engine), Some("ENGINE"));
+        assert!(d.address("BIG_BITE").is_none(), "an inline move is not an address");
+
+        let big_bite: U256 = "0x5009200000000000000000000000000000000000000000000000000000000000".parse().unwrap();
+        assert!(is_inline_move(big_bite));
+        assert_eq!(d.name_of_word(big_bite), Some("BIG_BITE"));
+
+        // A deployed move as a team slot holds it: address | MOVE_META_TAG | static stamina/priority.
+        let bull_rush = d.address("BULL_RUSH").unwrap();
+        let tagged = U256::from_be_slice(bull_rush.as_slice()) | (U256::from(1u8) << 160) | (U256::from(3u8) << 236);
+        assert!(!is_inline_move(tagged));
+        assert_eq!(d.name_of_word(tagged), Some("BULL_RUSH"));
+
+        // Inline abilities are reachable by their packed word and by the bare contract address.
+        let up_only: U256 = "0x010000000000000000000000c707825949fc0a992cbf18a60d66b604f02ed44b".parse().unwrap();
+        assert_eq!(d.name_of_word(up_only), Some("UP_ONLY"));
+        assert_eq!(d.name_of(low_address(up_only)), Some("UP_ONLY"));
+    }
+}
@@ -0,0 +1,225 @@
+//! The indexer's SQLite store: battles (seats, mode, start / end block, winner, turn count),
+//! per-battle effect applications, effect names, and the last indexed block.
+//!
+//! Every batch of [`Update`]s lands in one transaction together with the new cursor, so a killed
+//! indexer resumes from the last block it fully wrote. Addresses and keys are stored as their
+//! `Display` strings (checksummed addresses, 0x-hex keys).
+
+use alloy_primitives::{Address, B256};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS battles (
+    battle_key  TEXT PRIMARY KEY,
+    mode        INTEGER NOT NULL,
+    p0          TEXT NOT NULL,
+    p1          TEXT NOT NULL,
+    p2          TEXT,
+    p3          TEXT,
+    start_block INTEGER NOT NULL,
+    start_tx    TEXT NOT NULL,
+    end_block   INTEGER,
+    winner      TEXT,
+    turns       INTEGER
+);
+CREATE TABLE IF NOT EXISTS battle_effects (
+    battle_key   TEXT NOT NULL,
+    effect       TEXT NOT NULL,
+    applications INTEGER NOT NULL,
+    PRIMARY KEY (battle_key, effect)
+);
+CREATE TABLE IF NOT EXISTS effect_names (
+    effect TEXT PRIMARY KEY,
+    name   TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS cursor (
+    id         INTEGER PRIMARY KEY CHECK (id = 0),
+    last_block INTEGER NOT NULL
+);
+CREATE VIEW IF NOT EXISTS effect_usage AS
+    SELECT e.effect, n.name, COUNT(*) AS battles, SUM(e.applications) AS applications
+    FROM battle_effects e LEFT JOIN effect_names n ON n.effect = e.effect
+    GROUP BY e.effect;
+";
+
+/// One fact the indexer learned from a block range.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Update {
+    /// `BattleStart` (mode 0) or `SlotBattleStart`; `players` is p0, p1 (, p2, p3).
+    Start {
+        battle_key: B256,
+        mode: u8,
+        players: Vec<Address>,
+        block: u64,
+        tx: B256,
+    },
+    /// A completion event; `turns` from the engine's turn counter when it was read.
+    Complete {
+        battle_key: B256,
+        winner: Address,
+        block: u64,
+        turns: Option<u64>,
+    },
+    /// `count` more applications of `effect` in `battle_key`.
+    Effect {
+        battle_key: B256,
+        effect: Address,
+        count: u64,
+    },
+    EffectName {
+        effect: Address,
+        name: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffectUsage {
+    pub effect: String,
+    pub name: Option<String>,
+    pub battles: u64,
+    pub applications: u64,
+}
+
+pub struct Index {
+    conn: Connection,
+}
+
+impl Index {
+    pub fn open(path: &Path) -> rusqlite::Result<Index> {
+        Index::init(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> rusqlite::Result<Index> {
+        Index::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> rusqlite::Result<Index> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Index { conn })
+    }
+
+    /// The last block fully indexed, if any.
+    pub fn cursor(&self) -> rusqlite::Result<Option<u64>> {
+        self.conn.query_row("SELECT last_block FROM cursor WHERE id = 0", [], |r| r.get(0)).optional()
+    }
+
+    pub fn has_effect_name(&self, effect: Address) -> rusqlite::Result<bool> {
+        self.conn
+            .query_row("SELECT 1 FROM effect_names WHERE effect = ?1", [effect.to_string()], |_| Ok(()))
+            .optional()
+            .map(|r| r.is_some())
+    }
+
+    /// Writes `updates` and advances the cursor to `last_block`, atomically.
+    pub fn apply(&mut self, last_block: u64, updates: &[Update]) -> rusqlite::Result<()> {
+        let tx = self.conn.transaction()?;
+        for u in updates {
+            match u {
+                Update::Start { battle_key, mode, players, block, tx: hash } => {
+                    let seat = |i: usize| players.get(i).map(ToString::to_string);
+                    tx.execute(
+                        "INSERT OR REPLACE INTO battles (battle_key, mode, p0, p1, p2, p3, start_block, start_tx)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                        params![
+                            battle_key.to_string(),
+                            mode,
+                            seat(0).unwrap_or_default(),
+                            seat(1).unwrap_or_default(),
+                            seat(2),
+                            seat(3),
+                            block,
+                            hash.to_string()
+                        ],
+                    )?;
+                }
+                Update::Complete { battle_key, winner, block, turns } => {
+                    tx.execute(
+                        "UPDATE battles SET winner = ?2, end_block = ?3, turns = ?4 WHERE battle_key = ?1",
+                        params![battle_key.to_string(), winner.to_string(), block, turns],
+                    )?;
+                }
+                Update::Effect { battle_key, effect, count } => {
+                    tx.execute(
+                        "INSERT INTO battle_effects (battle_key, effect, applications) VALUES (?1, ?2, ?3)
+                         ON CONFLICT (battle_key, effect) DO UPDATE SET applications = applications + ?3",
+                        params![battle_key.to_string(), effect.to_string(), count],
+                    )?;
+                }
+                Update::EffectName { effect, name } => {
+                    tx.execute(
+                        "INSERT OR REPLACE INTO effect_names (effect, name) VALUES (?1, ?2)",
+                        params![effect.to_string(), name],
+                    )?;
+                }
+            }
+        }
+        tx.execute(
+            "INSERT INTO cursor (id, last_block) VALUES (0, ?1) ON CONFLICT (id) DO UPDATE SET last_block = ?1",
+            [last_block],
+        )?;
+        tx.commit()
+    }
+
+    /// `(battles started, battles completed)`.
+    pub fn battle_counts(&self) -> rusqlite::Result<(u64, u64)> {
+        self.conn.query_row("SELECT COUNT(*), COUNT(winner) FROM battles", [], |r| Ok((r.get(0)?, r.get(1)?)))
+    }
+
+    /// Effects by how many battles used them, then by total applications.
+    pub fn effect_usage(&self) -> rusqlite::Result<Vec<EffectUsage>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT effect, name, battles, applications FROM effect_usage ORDER BY battles DESC, applications DESC",
+        )?;
+        let rows = stmt.query_map([], |r| {
+            Ok(EffectUsage { effect: r.get(0)?, name: r.get(1)?, battles: r.get(2)?, applications: r.get(3)? })
+        })?;
+        rows.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_accumulate_and_resume_from_the_cursor() {
+        let mut ix = Index::in_memory().unwrap();
+        assert_eq!(ix.cursor().unwrap(), None);
+        let (k1, k2) = (B256::repeat_byte(1), B256::repeat_byte(2));
+        let (p0, p1) = (Address::repeat_byte(0xa0), Address::repeat_byte(0xa1));
+        let (burn, frost) = (Address::repeat_byte(0xb0), Address::repeat_byte(0xf0));
+        let start =
+            |battle_key| Update::Start { battle_key, mode: 0, players: vec![p0, p1], block: 10, tx: B256::ZERO };
+        ix.apply(
+            19,
+            &[
+                start(k1),
+                start(k2),
+                Update::Effect { battle_key: k1, effect: burn, count: 2 },
+                Update::Effect { battle_key: k2, effect: burn, count: 1 },
+                Update::EffectName { effect: burn, name: "BurnStatus".into() },
+            ],
+        )
+        .unwrap();
+        ix.apply(
+            29,
+            &[
+                Update::Effect { battle_key: k1, effect: burn, count: 1 },
+                Update::Effect { battle_key: k1, effect: frost, count: 1 },
+                Update::Complete { battle_key: k1, winner: p1, block: 25, turns: Some(7) },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(ix.cursor().unwrap(), Some(29));
+        assert_eq!(ix.battle_counts().unwrap(), (2, 1));
+        assert!(ix.has_effect_name(burn).unwrap() && !ix.has_effect_name(frost).unwrap());
+        let usage = ix.effect_usage().unwrap();
+        assert_eq!(
+            usage[0],
+            EffectUsage { effect: burn.to_string(), name: Some("BurnStatus".into()), battles: 2, applications: 4 }
+        );
+        assert_eq!((usage[1].name.as_deref(), usage[1].battles, usage[1].applications), (None, 1, 1));
+    }
+}
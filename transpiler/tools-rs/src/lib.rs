@@ -0,0 +1,14 @@
+//! On-chain tooling: everything here reads a live (or archive) node rather than the transpiled
+//! engine. [`rpc`] is the provider wrapper, [`deployments`] names addresses from the repo's
+//! deployment record, [`timeline`] turns engine events plus `getBattle` diffs into turns, and
+//! [`replay`] rebuilds the moves from the battle's mined calldata. [`index`] is the indexer's
+//! SQLite store, [`storage`] decodes raw contract storage through a Foundry layout, and [`teams`]
+//! shapes registry teams for export.
+
+pub mod deployments;
+pub mod index;
+pub mod replay;
+pub mod rpc;
+pub mod storage;
+pub mod teams;
+pub mod timeline;
@@ -0,0 +1,272 @@
+//! Battle reconstruction from mined calldata: every transaction that touched a battle key,
+//! decoded with [`chomp_strategies::calldata::decode_submission`] and folded back into the
+//! turn-by-turn moves both sides played — the JSON `bin/replay` prints.
+//!
+//! Turn numbering follows the engine. Built-in buffer submissions take `turnId + numBuffered`,
+//! i.e. one turn per successful submission in mined order, whether or not it executed yet; the
+//! committer is the sender (p0 on even turns, p1 on odd — or, in 2-slot mode, whichever side
+//! the sender sits on). LEGACY commit / reveal turns close at the transaction that executed them
+//! (an `EngineExecute` in its receipt), and each reveal is checked against the sender's commit for
+//! that turn with [`chomp_strategies::commit::verify_move`].
+
+use alloy_primitives::{Address, B256};
+use chomp_engine::Constants::{NO_OP_MOVE_INDEX, SWITCH_MOVE_INDEX};
+use chomp_rt::U256;
+use chomp_strategies::calldata::{decode_submission, unpack_turn_half, Submission};
+use chomp_strategies::commit::verify_move;
+use chomp_strategies::sim::unpack_side;
+use serde::Serialize;
+
+/// One successful transaction the battle's logs point at.
+#[derive(Clone, Debug)]
+pub struct MinedTx {
+    pub hash: B256,
+    pub block: u64,
+    pub from: Address,
+    pub input: Vec<u8>,
+    /// The receipt carries an `EngineExecute` / completion event for the battle.
+    pub executed: bool,
+    /// For a single-player turn sent by a move manager rather than a seat: the side
+    /// `playerSwitchForTurnFlag` named going into the transaction.
+    pub forced_side: Option<usize>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SideMove {
+    pub side: usize,
+    pub slot: usize,
+    /// `move`, `switch` or `no-op`.
+    pub action: &'static str,
+    /// The move slot for `move`, the incoming team slot for `switch`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<u16>,
+    pub extra_data: u16,
+    pub salt: String,
+    /// LEGACY only: whether the reveal opened the sender's commitment (absent when the sender
+    /// revealed without committing, as the non-committing side does every turn).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment_verified: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Turn {
+    pub turn: u64,
+    pub block: u64,
+    pub txs: Vec<String>,
+    pub executed: bool,
+    pub moves: Vec<SideMove>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Replay {
+    pub battle_key: String,
+    pub mode: &'static str,
+    pub players: Vec<String>,
+    pub turns: Vec<Turn>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winner: Option<String>,
+    /// Transactions in the battle's logs whose input is not a move (matchmaking, forfeits, …).
+    pub other_txs: Vec<String>,
+}
+
+fn side_move(side: usize, slot: usize, move_index: u8, extra_data: u16, salt: u128) -> SideMove {
+    let (action, index) = match move_index {
+        SWITCH_MOVE_INDEX => ("switch", Some(extra_data)),
+        NO_OP_MOVE_INDEX => ("no-op", None),
+        m => ("move", Some(m as u16)),
+    };
+    SideMove { side, slot, action, index, extra_data, salt: format!("{salt:#x}"), commitment_verified: None }
+}
+
+fn half_move(side: usize, half: U256) -> SideMove {
+    let (m, salt, extra) = unpack_turn_half(half);
+    side_move(side, 0, m, extra, salt)
+}
+
+fn side_moves(side: usize, word: U256) -> Vec<SideMove> {
+    let (slots, salt) = unpack_side(word);
+    slots.iter().enumerate().map(|(slot, &(m, e))| side_move(side, slot, m, e, salt)).collect()
+}
+
+fn note(t: &mut Turn, tx: &MinedTx) {
+    let hash = tx.hash.to_string();
+    if !t.txs.contains(&hash) {
+        t.txs.push(hash);
+    }
+}
+
+/// `players` is the seat list from `BattleStart` (p0, p1) or `SlotBattleStart` (p0, p1, p2, p3);
+/// side 0 is p0 (+ p2).
+pub fn reconstruct(
+    battle_key: B256,
+    players: &[Address],
+    slots: bool,
+    txs: &[MinedTx],
+    winner: Option<Address>,
+) -> Replay {
+    let seat_side = |from: Address| players.iter().position(|&p| p == from).map(|i| i % 2);
+    let mut turns: Vec<Turn> = Vec::new();
+    let mut other_txs = Vec::new();
+    // The LEGACY turn being assembled, with each side's commitment so far.
+    let mut legacy: Option<(Turn, [Option<chomp_rt::B256>; 2])> = None;
+    let mut next_turn = 0u64;
+
+    for tx in txs {
+        let Some(sub) = decode_submission(&tx.input).filter(|s| s.battle_key().as_slice() == battle_key.as_slice())
+        else {
+            other_txs.push(tx.hash.to_string());
+            continue;
+        };
+        let turn = |n: u64, executed: bool, moves: Vec<SideMove>| Turn {
+            turn: n,
+            block: tx.block,
+            txs: vec![tx.hash.to_string()],
+            executed,
+            moves,
+        };
+        let committer = seat_side(tx.from).unwrap_or(0);
+        match sub {
+            Submission::Turn { committer: c, revealer: r, execute, .. } => {
+                let mut moves = vec![half_move(committer, c), half_move(1 - committer, r)];
+                moves.sort_by_key(|m| m.side);
+                turns.push(turn(next_turn, execute, moves));
+                next_turn += 1;
+            }
+            Submission::SlotTurn { committer_side, revealer_side, execute, .. } => {
+                let (s0, s1) =
+                    if committer == 0 { (committer_side, revealer_side) } else { (revealer_side, committer_side) };
+                turns.push(turn(next_turn, execute, [side_moves(0, s0), side_moves(1, s1)].concat()));
+                next_turn += 1;
+            }
+            Submission::Moves { p0, p1, .. } => {
+                turns.push(turn(next_turn, true, vec![half_move(0, p0), half_move(1, p1)]));
+                next_turn += 1;
+            }
+            Submission::SingleMove { half, .. } => {
+                let side = seat_side(tx.from).or(tx.forced_side).unwrap_or(0);
+                turns.push(turn(next_turn, true, vec![half_move(side, half)]));
+                next_turn += 1;
+            }
+            Submission::SlotMoves { side0, side1, .. } => {
+                turns.push(turn(next_turn, true, [side_moves(0, side0), side_moves(1, side1)].concat()));
+                next_turn += 1;
+            }
+            Submission::Batched { entries, slots: batched_slots, .. } => {
+                for e in entries {
+                    let moves = if batched_slots {
+                        [side_moves(0, e & U256::from(u128::MAX)), side_moves(1, e >> 128)].concat()
+                    } else {
+                        vec![half_move(0, e & U256::from(u128::MAX)), half_move(1, e >> 128)]
+                    };
+                    turns.push(turn(next_turn, true, moves));
+                    next_turn += 1;
+                }
+            }
+            Submission::Execute { .. } => {
+                for t in turns.iter_mut().filter(|t| !t.executed) {
+                    t.executed = true;
+                    t.txs.push(tx.hash.to_string());
+                }
+            }
+            Submission::Commit { move_hash, .. } => {
+                let (t, commits) = legacy.get_or_insert_with(|| (turn(next_turn, false, Vec::new()), [None; 2]));
+                commits[committer] = Some(move_hash);
+                note(t, tx);
+            }
+            Submission::Reveal { half, .. } => {
+                let (t, commits) = legacy.get_or_insert_with(|| (turn(next_turn, false, Vec::new()), [None; 2]));
+                let (m, salt, extra) = unpack_turn_half(half);
+                let mut mv = side_move(committer, 0, m, extra, salt);
+                mv.commitment_verified = commits[committer].map(|h| verify_move(h, m, salt, extra));
+                t.moves.push(mv);
+                note(t, tx);
+            }
+        }
+        // A LEGACY turn ends in whichever transaction executes it (auto-execute reveal or a
+        // standalone `execute`).
+        if tx.executed {
+            if let Some((mut t, _)) = legacy.take() {
+                note(&mut t, tx);
+                t.executed = true;
+                t.moves.sort_by_key(|m| m.side);
+                turns.push(t);
+                next_turn += 1;
+            }
+        }
+    }
+    turns.extend(legacy.map(|(t, _)| t));
+
+    Replay {
+        battle_key: battle_key.to_string(),
+        mode: if slots { "slots" } else { "singles" },
+        players: players.iter().map(ToString::to_string).collect(),
+        turns,
+        winner: winner.map(|w| w.to_string()),
+        other_txs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chomp_strategies::calldata::{
+        commit_move, pack_turn_half, pack_turn_moves, reveal_move, reveal_switch, submit_turn_moves,
+    };
+    use chomp_strategies::commit::move_hash;
+
+    fn mined(n: u8, from: Address, input: Vec<u8>, executed: bool) -> MinedTx {
+        MinedTx { hash: B256::repeat_byte(n), block: 100 + n as u64, from, input, executed, forced_side: None }
+    }
+
+    #[test]
+    fn builtin_turns_alternate_committer() {
+        let key = B256::repeat_byte(0x42);
+        let rt_key = chomp_rt::B256::from_slice(key.as_slice());
+        let (p0, p1) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let z = chomp_rt::B256::ZERO;
+        // Turn 0: p0 commits move 1, p1 reveals a switch to mon 2. Turn 1: p1 commits, p0 reveals.
+        let t0 = pack_turn_moves(pack_turn_half(1, 5, 0), pack_turn_half(SWITCH_MOVE_INDEX, 6, 2));
+        let t1 = pack_turn_moves(pack_turn_half(NO_OP_MOVE_INDEX, 7, 0), pack_turn_half(0, 8, 0));
+        let txs = [
+            mined(1, p0, submit_turn_moves(rt_key, t0, z, z, false), false),
+            mined(2, p1, submit_turn_moves(rt_key, t1, z, z, true), true),
+        ];
+        let r = reconstruct(key, &[p0, p1], false, &txs, Some(p0));
+        assert_eq!(r.turns.len(), 2);
+        let t = &r.turns[0];
+        assert!(!t.executed);
+        assert_eq!((t.moves[0].side, t.moves[0].action, t.moves[0].index), (0, "move", Some(1)));
+        assert_eq!((t.moves[1].side, t.moves[1].action, t.moves[1].index), (1, "switch", Some(2)));
+        let t = &r.turns[1];
+        assert!(t.executed);
+        assert_eq!((t.moves[0].side, t.moves[0].action, t.moves[0].salt.as_str()), (0, "move", "0x8"));
+        assert_eq!((t.moves[1].side, t.moves[1].action), (1, "no-op"));
+    }
+
+    #[test]
+    fn legacy_reveals_are_checked_against_commitments() {
+        let key = B256::repeat_byte(0x42);
+        let rt_key = chomp_rt::B256::from_slice(key.as_slice());
+        let (p0, p1) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let txs = [
+            mined(1, p0, commit_move(rt_key, move_hash(2, 11, 0)), false),
+            mined(2, p1, reveal_switch(rt_key, 1, 12, false), false),
+            mined(3, p0, reveal_move(rt_key, 2, 11, 0, true), true),
+            // Turn 1: p1 commits but reveals a different salt.
+            mined(4, p1, commit_move(rt_key, move_hash(0, 13, 0)), false),
+            mined(5, p0, reveal_move(rt_key, 3, 14, 0, false), false),
+            mined(6, p1, reveal_move(rt_key, 0, 99, 0, true), true),
+        ];
+        let r = reconstruct(key, &[p0, p1], false, &txs, None);
+        assert_eq!(r.turns.len(), 2);
+        assert_eq!(r.turns[0].txs.len(), 3);
+        assert_eq!(r.turns[0].moves[0].commitment_verified, Some(true));
+        assert_eq!(r.turns[0].moves[1].commitment_verified, None);
+        assert_eq!(r.turns[1].turn, 1);
+        assert_eq!(r.turns[1].moves[1].commitment_verified, Some(false));
+        assert!(r.other_txs.is_empty());
+    }
+}
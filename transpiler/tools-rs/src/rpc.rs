@@ -0,0 +1,119 @@
+//! The one place the tools touch a node: an HTTP provider, typed `eth_call`s through the
+//! [`chomp_bindings`] declarations, paged `eth_getLogs`, and on-chain `name()` fallbacks for
+//! addresses `deployments.json` does not know.
+//!
+//! Historical reads pin a block, so they need an archive node for anything past its pruning
+//! window — battle storage is recycled once a battle ends, so "latest" is not a substitute.
+
+use crate::deployments::Deployments;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_provider::{Provider, ProviderBuilder, RootProvider};
+use alloy_rpc_types_eth::{BlockId, Filter, Log, TransactionRequest, TransactionTrait};
+use chomp_bindings::alloy_sol_types::SolCall;
+use chomp_bindings::{IEffect, IMoveSet};
+use std::collections::HashMap;
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Default `eth_getLogs` window; public endpoints commonly cap ranges at 10k blocks.
+pub const LOG_CHUNK: u64 = 10_000;
+
+pub struct Rpc {
+    pub provider: RootProvider,
+}
+
+impl Rpc {
+    pub fn connect(url: &str) -> Result<Rpc> {
+        let provider = ProviderBuilder::new().disable_recommended_fillers().connect_http(url.parse()?);
+        Ok(Rpc { provider })
+    }
+
+    /// `eth_call` of `call` against `to`, at `block` (None = latest).
+    pub async fn call<C: SolCall>(&self, to: Address, call: C, block: Option<u64>) -> Result<C::Return> {
+        let tx = TransactionRequest::default().to(to).input(Bytes::from(call.abi_encode()).into());
+        let block = block.map(BlockId::number).unwrap_or_else(BlockId::latest);
+        let out = self.provider.call(tx).block(block).await?;
+        Ok(C::abi_decode_returns(&out)?)
+    }
+
+    pub async fn head(&self) -> Result<u64> {
+        Ok(self.provider.get_block_number().await?)
+    }
+
+    /// Every log `filter` matches over `[from, to]`, fetched `chunk` blocks at a time.
+    pub async fn logs(&self, filter: Filter, from: u64, to: u64, chunk: u64) -> Result<Vec<Log>> {
+        let mut out = Vec::new();
+        let mut start = from;
+        while start <= to {
+            let end = to.min(start.saturating_add(chunk.max(1) - 1));
+            out.extend(self.provider.get_logs(&filter.clone().from_block(start).to_block(end)).await?);
+            start = end + 1;
+        }
+        Ok(out)
+    }
+
+    /// A mined transaction's sender and input.
+    pub async fn transaction(&self, hash: B256) -> Result<(Address, Bytes)> {
+        let tx = self.provider.get_transaction_by_hash(hash).await?.ok_or_else(|| format!("no transaction {hash}"))?;
+        Ok((tx.inner.signer(), tx.inner.input().clone()))
+    }
+
+    pub async fn storage_at(&self, contract: Address, slot: U256, block: Option<u64>) -> Result<B256> {
+        let block = block.map(BlockId::number).unwrap_or_else(BlockId::latest);
+        Ok(self.provider.get_storage_at(contract, slot).block_id(block).await?.into())
+    }
+}
+
+/// Names for contracts and move slots: `deployments.json` first, then the contract's own
+/// `name()`, then the raw hex. Lookups are cached for the life of the resolver.
+pub struct Resolver {
+    pub deployments: Deployments,
+    cache: HashMap<Address, String>,
+}
+
+impl Resolver {
+    pub fn new(deployments: Deployments) -> Resolver {
+        Resolver { deployments, cache: HashMap::new() }
+    }
+
+    pub async fn effect(&mut self, rpc: &Rpc, addr: Address) -> String {
+        if let Some(n) = self.deployments.name_of(addr) {
+            return n.to_string();
+        }
+        if let Some(n) = self.cache.get(&addr) {
+            return n.clone();
+        }
+        let name = rpc.call(addr, IEffect::nameCall {}, None).await.unwrap_or_else(|_| addr.to_string());
+        self.cache.insert(addr, name.clone());
+        name
+    }
+
+    /// A `Mon.ability` word (a bare or type-tagged address; 0 is no ability).
+    pub async fn ability(&mut self, rpc: &Rpc, word: U256) -> String {
+        if word.is_zero() {
+            return "none".to_string();
+        }
+        if let Some(n) = self.deployments.name_of_word(word) {
+            return n.to_string();
+        }
+        self.effect(rpc, Address::from_word(word.to_be_bytes::<32>().into())).await
+    }
+
+    /// A `Mon.moves` slot word; inline moves unknown to the record print as their raw word.
+    pub async fn move_slot(&mut self, rpc: &Rpc, word: U256) -> String {
+        if let Some(n) = self.deployments.name_of_word(word) {
+            return n.to_string();
+        }
+        if crate::deployments::is_inline_move(word) {
+            return format!("inline {word:#x}");
+        }
+        let addr = Address::from_word(word.to_be_bytes::<32>().into());
+        if let Some(n) = self.cache.get(&addr) {
+            return n.clone();
+        }
+        let name = rpc.call(addr, IMoveSet::nameCall {}, None).await.unwrap_or_else(|_| addr.to_string());
+        self.cache.insert(addr, name.clone());
+        name
+    }
+}
@@ -0,0 +1,376 @@
+//! Raw-storage reads against a Foundry storage layout: resolve a Solidity access path
+//! (`battleConfig[0x…].p0States[1]`) to its slot and byte offset, and decode what is there into
+//! JSON — no view function needed, so anything the contract keeps private is readable too.
+//!
+//! The layout is solc's `storageLayout` output, either as `forge inspect <Contract> storageLayout
+//! --json` prints it or inside a build artifact compiled with `extra_output = ["storageLayout"]`.
+//! Slots are read through the [`Storage`] trait; the bins back it with `eth_getStorageAt`.
+
+use alloy_primitives::{keccak256, U256};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Dynamic arrays longer than this decode only their first elements.
+pub const MAX_ARRAY_ELEMENTS: usize = 64;
+
+pub trait Storage {
+    fn load(&mut self, slot: U256) -> Result<U256, String>;
+}
+
+impl Storage for HashMap<U256, U256> {
+    fn load(&mut self, slot: U256) -> Result<U256, String> {
+        Ok(self.get(&slot).copied().unwrap_or_default())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Var {
+    label: String,
+    slot: U256,
+    offset: usize,
+    ty: String,
+}
+
+#[derive(Clone, Debug, Default)]
+struct TypeInfo {
+    encoding: String,
+    label: String,
+    bytes: usize,
+    members: Vec<Var>,
+    key: Option<String>,
+    value: Option<String>,
+    base: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Layout {
+    vars: Vec<Var>,
+    types: HashMap<String, TypeInfo>,
+}
+
+/// A resolved storage location: where a value of `ty` starts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub slot: U256,
+    pub offset: usize,
+    pub ty: String,
+}
+
+fn parse_var(v: &Value) -> Result<Var, String> {
+    let s = |k: &str| v.get(k).and_then(Value::as_str).ok_or_else(|| format!("storage entry without {k}: {v}"));
+    Ok(Var {
+        label: s("label")?.to_string(),
+        slot: s("slot")?.parse().map_err(|_| format!("bad slot in {v}"))?,
+        offset: v.get("offset").and_then(Value::as_u64).unwrap_or(0) as usize,
+        ty: s("type")?.to_string(),
+    })
+}
+
+/// One path step: `.field` or `[key]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Step {
+    Field(String),
+    Index(String),
+}
+
+fn parse_path(path: &str) -> Result<Vec<Step>, String> {
+    let mut steps = Vec::new();
+    let mut rest = path;
+    while !rest.is_empty() {
+        if let Some(r) = rest.strip_prefix('[') {
+            let end = r.find(']').ok_or_else(|| format!("unclosed [ in {path}"))?;
+            steps.push(Step::Index(r[..end].trim().to_string()));
+            rest = &r[end + 1..];
+        } else {
+            let r = rest.strip_prefix('.').unwrap_or(rest);
+            let end = r.find(['.', '[']).unwrap_or(r.len());
+            if end == 0 {
+                return Err(format!("empty field name in {path}"));
+            }
+            steps.push(Step::Field(r[..end].to_string()));
+            rest = &r[end..];
+        }
+    }
+    Ok(steps)
+}
+
+/// `t_bytes4` → 4.
+fn fixed_bytes_len(ty: &str) -> Option<usize> {
+    ty.strip_prefix("t_bytes")?.parse().ok()
+}
+
+/// `t_array(t_uint16)3_storage` → 3; `None` for dynamic arrays.
+fn static_len(ty: &str) -> Option<usize> {
+    ty.rsplit_once(')')?.1.split('_').next()?.parse().ok()
+}
+
+/// A mapping key as the 32-byte word solc hashes: value types left-padded, bytesN right-padded.
+fn key_word(key_ty: &str, key: &str) -> Result<U256, String> {
+    let bad = || format!("key {key} does not fit {key_ty}");
+    if let Some(n) = fixed_bytes_len(key_ty) {
+        let hex = key.strip_prefix("0x").ok_or_else(bad)?;
+        let v = U256::from_str_radix(hex, 16).map_err(|_| bad())?;
+        if hex.len() > 2 * n {
+            return Err(bad());
+        }
+        return Ok(v << (8 * (32 - n) + 4 * (2 * n - hex.len())));
+    }
+    if key_ty.starts_with("t_int") {
+        let v: i128 = key.parse().map_err(|_| bad())?;
+        return Ok(if v < 0 { U256::MAX - U256::from(v.unsigned_abs() - 1) } else { U256::from(v as u128) });
+    }
+    if key_ty == "t_bool" {
+        return Ok(U256::from((key == "true") as u8));
+    }
+    // address / contract / uint / enum: a plain number, decimal or 0x-hex.
+    match key.strip_prefix("0x") {
+        Some(hex) => U256::from_str_radix(hex, 16).map_err(|_| bad()),
+        None => key.parse().map_err(|_| bad()),
+    }
+}
+
+fn mapping_slot(key: U256, slot: U256) -> U256 {
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(&key.to_be_bytes::<32>());
+    buf[32..].copy_from_slice(&slot.to_be_bytes::<32>());
+    keccak256(buf).into()
+}
+
+fn array_data_slot(slot: U256) -> U256 {
+    keccak256(slot.to_be_bytes::<32>()).into()
+}
+
+impl Layout {
+    /// Accepts `forge inspect … storageLayout --json` output or a whole artifact carrying it.
+    pub fn parse(text: &str) -> Result<Layout, String> {
+        let doc: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        let doc = doc.get("storageLayout").unwrap_or(&doc);
+        let storage = doc
+            .get("storage")
+            .and_then(Value::as_array)
+            .ok_or("no storage layout (want `forge inspect … storageLayout --json` or an artifact with it)")?;
+        let mut layout =
+            Layout { vars: storage.iter().map(parse_var).collect::<Result<_, _>>()?, ..Default::default() };
+        for (name, t) in doc.get("types").and_then(Value::as_object).into_iter().flatten() {
+            let s = |k: &str| t.get(k).and_then(Value::as_str).map(str::to_string);
+            let members = match t.get("members").and_then(Value::as_array) {
+                Some(m) => m.iter().map(parse_var).collect::<Result<_, _>>()?,
+                None => Vec::new(),
+            };
+            let info = TypeInfo {
+                encoding: s("encoding").unwrap_or_default(),
+                label: s("label").unwrap_or_default(),
+                bytes: s("numberOfBytes").and_then(|n| n.parse().ok()).unwrap_or(32),
+                members,
+                key: s("key"),
+                value: s("value"),
+                base: s("base"),
+            };
+            layout.types.insert(name.clone(), info);
+        }
+        Ok(layout)
+    }
+
+    fn ty(&self, ty: &str) -> Result<&TypeInfo, String> {
+        self.types.get(ty).ok_or_else(|| format!("type {ty} missing from the layout"))
+    }
+
+    /// Slots one element of `ty` occupies when it cannot pack (structs, arrays, 32-byte values).
+    fn slots_of(&self, ty: &str) -> Result<usize, String> {
+        Ok(self.ty(ty)?.bytes.div_ceil(32))
+    }
+
+    /// Where element `i` of a static or dynamic array whose data starts at `base` lives.
+    fn element(&self, elem_ty: &str, base: U256, i: usize) -> Result<(U256, usize), String> {
+        let size = self.ty(elem_ty)?.bytes;
+        if size <= 16 {
+            let per_slot = 32 / size;
+            Ok((base + U256::from(i / per_slot), (i % per_slot) * size))
+        } else {
+            Ok((base + U256::from(i * self.slots_of(elem_ty)?), 0))
+        }
+    }
+
+    /// Resolves `path` (`var`, `var[key]`, `var.field`, `var[k1][k2].field[3]` …). Dynamic array
+    /// indices read the array's length slot to bounds-check.
+    pub fn locate(&self, path: &str, storage: &mut dyn Storage) -> Result<Location, String> {
+        let steps = parse_path(path)?;
+        let Some(Step::Field(root)) = steps.first() else { return Err(format!("{path}: must start with a variable")) };
+        let var = self.vars.iter().find(|v| &v.label == root).ok_or_else(|| format!("no state variable {root}"))?;
+        let mut loc = Location { slot: var.slot, offset: var.offset, ty: var.ty.clone() };
+        for step in &steps[1..] {
+            let t = self.ty(&loc.ty)?;
+            loc = match (step, t.encoding.as_str()) {
+                (Step::Field(f), "inplace") if !t.members.is_empty() => {
+                    let m =
+                        t.members.iter().find(|m| &m.label == f).ok_or_else(|| format!("{} has no {f}", t.label))?;
+                    Location { slot: loc.slot + m.slot, offset: m.offset, ty: m.ty.clone() }
+                }
+                (Step::Index(k), "mapping") => {
+                    let (key_ty, value_ty) = (t.key.clone().unwrap_or_default(), t.value.clone().unwrap_or_default());
+                    Location { slot: mapping_slot(key_word(&key_ty, k)?, loc.slot), offset: 0, ty: value_ty }
+                }
+                (Step::Index(k), enc @ ("inplace" | "dynamic_array")) if t.base.is_some() => {
+                    let i: usize = k.parse().map_err(|_| format!("array index {k} is not a number"))?;
+                    let base_ty = t.base.clone().unwrap_or_default();
+                    let (start, len) = if enc == "dynamic_array" {
+                        (array_data_slot(loc.slot), storage.load(loc.slot)?.saturating_to::<usize>())
+                    } else {
+                        (loc.slot, static_len(&loc.ty).unwrap_or(0))
+                    };
+                    if i >= len {
+                        return Err(format!("index {i} out of bounds for {} (length {len})", t.label));
+                    }
+                    let (slot, offset) = self.element(&base_ty, start, i)?;
+                    Location { slot, offset, ty: base_ty }
+                }
+                (step, _) => return Err(format!("cannot apply {step:?} to {}", t.label)),
+            };
+        }
+        Ok(loc)
+    }
+
+    /// Decodes the value at `loc` into JSON: numbers as decimal strings (they overflow f64),
+    /// addresses and bytes as hex, structs as objects, arrays as lists (dynamic ones capped at
+    /// [`MAX_ARRAY_ELEMENTS`]); mappings cannot be enumerated and decode as `"<mapping>"`.
+    pub fn decode(&self, loc: &Location, storage: &mut dyn Storage) -> Result<Value, String> {
+        let t = self.ty(&loc.ty)?;
+        match t.encoding.as_str() {
+            "mapping" => Ok(json!("<mapping>")),
+            "bytes" => {
+                let word = storage.load(loc.slot)?;
+                let bytes = if word.bit(0) {
+                    let len = (word >> 1usize).saturating_to::<usize>();
+                    let data = array_data_slot(loc.slot);
+                    let mut out = Vec::with_capacity(len);
+                    for i in 0..len.div_ceil(32) {
+                        out.extend(storage.load(data + U256::from(i))?.to_be_bytes::<32>());
+                    }
+                    out.truncate(len);
+                    out
+                } else {
+                    word.to_be_bytes::<32>()[..(word.byte(0) as usize / 2)].to_vec()
+                };
+                Ok(match (t.label.as_str(), String::from_utf8(bytes.clone())) {
+                    ("string", Ok(s)) => json!(s),
+                    _ => json!(format!("0x{}", bytes.iter().map(|b| format!("{b:02x}")).collect::<String>())),
+                })
+            }
+            "dynamic_array" => {
+                let len = storage.load(loc.slot)?.saturating_to::<usize>();
+                let base = t.base.clone().unwrap_or_default();
+                let start = array_data_slot(loc.slot);
+                let mut items = Vec::new();
+                for i in 0..len.min(MAX_ARRAY_ELEMENTS) {
+                    let (slot, offset) = self.element(&base, start, i)?;
+                    items.push(self.decode(&Location { slot, offset, ty: base.clone() }, storage)?);
+                }
+                Ok(json!(items))
+            }
+            _ if !t.members.is_empty() => {
+                let mut obj = serde_json::Map::new();
+                for m in &t.members {
+                    let at = Location { slot: loc.slot + m.slot, offset: m.offset, ty: m.ty.clone() };
+                    obj.insert(m.label.clone(), self.decode(&at, storage)?);
+                }
+                Ok(Value::Object(obj))
+            }
+            _ if t.base.is_some() => {
+                let base = t.base.clone().unwrap_or_default();
+                let len = static_len(&loc.ty).unwrap_or(0);
+                let mut items = Vec::with_capacity(len);
+                for i in 0..len {
+                    let (slot, offset) = self.element(&base, loc.slot, i)?;
+                    items.push(self.decode(&Location { slot, offset, ty: base.clone() }, storage)?);
+                }
+                Ok(json!(items))
+            }
+            _ => Ok(self.elementary(loc, t, storage.load(loc.slot)?)),
+        }
+    }
+
+    fn elementary(&self, loc: &Location, t: &TypeInfo, word: U256) -> Value {
+        let bits = 8 * t.bytes;
+        let raw = (word >> (8 * loc.offset))
+            & if bits >= 256 { U256::MAX } else { (U256::from(1u8) << bits) - U256::from(1u8) };
+        let ty = loc.ty.as_str();
+        if ty == "t_bool" {
+            json!(!raw.is_zero())
+        } else if ty == "t_address" || ty.starts_with("t_contract") || ty == "t_address_payable" {
+            json!(alloy_primitives::Address::from_word(raw.to_be_bytes::<32>().into()).to_string())
+        } else if ty.starts_with("t_int") {
+            let negative = bits < 256 && raw.bit(bits - 1);
+            if negative {
+                let magnitude = (U256::from(1u8) << bits) - raw;
+                json!(format!("-{magnitude}"))
+            } else {
+                json!(raw.to_string())
+            }
+        } else if ty.starts_with("t_bytes") {
+            let b = raw.to_be_bytes::<32>();
+            json!(format!("0x{}", b[32 - t.bytes..].iter().map(|b| format!("{b:02x}")).collect::<String>()))
+        } else {
+            json!(raw.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A trimmed `forge inspect` layout: `mapping(bytes32 => S) m; uint8[] a;` with
+    /// `struct S { int32 x; bool k; uint16[3] v; address who; }`.
+    const LAYOUT: &str = r#"{
+      "storage": [
+        {"label": "m", "offset": 0, "slot": "3", "type": "t_mapping(t_bytes32,t_struct(S)1_storage)"},
+        {"label": "a", "offset": 0, "slot": "4", "type": "t_array(t_uint8)dyn_storage"}
+      ],
+      "types": {
+        "t_bytes32": {"encoding": "inplace", "label": "bytes32", "numberOfBytes": "32"},
+        "t_int32": {"encoding": "inplace", "label": "int32", "numberOfBytes": "4"},
+        "t_bool": {"encoding": "inplace", "label": "bool", "numberOfBytes": "1"},
+        "t_uint8": {"encoding": "inplace", "label": "uint8", "numberOfBytes": "1"},
+        "t_uint16": {"encoding": "inplace", "label": "uint16", "numberOfBytes": "2"},
+        "t_address": {"encoding": "inplace", "label": "address", "numberOfBytes": "20"},
+        "t_array(t_uint16)3_storage": {"encoding": "inplace", "label": "uint16[3]", "numberOfBytes": "32", "base": "t_uint16"},
+        "t_array(t_uint8)dyn_storage": {"encoding": "dynamic_array", "label": "uint8[]", "numberOfBytes": "32", "base": "t_uint8"},
+        "t_mapping(t_bytes32,t_struct(S)1_storage)": {"encoding": "mapping", "key": "t_bytes32", "label": "mapping(bytes32 => struct S)", "numberOfBytes": "32", "value": "t_struct(S)1_storage"},
+        "t_struct(S)1_storage": {"encoding": "inplace", "label": "struct S", "numberOfBytes": "96", "members": [
+          {"label": "x", "offset": 0, "slot": "0", "type": "t_int32"},
+          {"label": "k", "offset": 4, "slot": "0", "type": "t_bool"},
+          {"label": "v", "offset": 0, "slot": "1", "type": "t_array(t_uint16)3_storage"},
+          {"label": "who", "offset": 0, "slot": "2", "type": "t_address"}
+        ]}
+      }
+    }"#;
+
+    #[test]
+    fn locates_and_decodes_nested_paths() {
+        let layout = Layout::parse(LAYOUT).unwrap();
+        let key = "0x".to_string() + &"11".repeat(32);
+        let base = mapping_slot(U256::from_be_bytes([0x11; 32]), U256::from(3u8));
+        let mut st: HashMap<U256, U256> = HashMap::new();
+        // x = -5 (int32 two's complement), k = true at byte 4.
+        st.insert(base, U256::from(0xffff_fffbu32) | (U256::from(1u8) << 32));
+        st.insert(base + U256::from(1u8), U256::from(7u8) | (U256::from(9u8) << 32));
+        st.insert(base + U256::from(2u8), U256::from(0xabu8));
+        st.insert(U256::from(4u8), U256::from(2u8));
+        st.insert(array_data_slot(U256::from(4u8)), U256::from(0x0201u16));
+
+        let loc = layout.locate(&format!("m[{key}]"), &mut st).unwrap();
+        assert_eq!(loc.slot, base);
+        let v = layout.decode(&loc, &mut st).unwrap();
+        assert_eq!(v["x"], "-5");
+        assert_eq!(v["k"], true);
+        assert_eq!(v["v"], json!(["7", "0", "9"]));
+        assert_eq!(v["who"], alloy_primitives::Address::with_last_byte(0xab).to_string());
+
+        let v2 = layout.locate(&format!("m[{key}].v[2]"), &mut st).unwrap();
+        assert_eq!((v2.slot, v2.offset), (base + U256::from(1u8), 4));
+        assert_eq!(layout.decode(&layout.locate("a", &mut st).unwrap(), &mut st).unwrap(), json!(["1", "2"]));
+        assert_eq!(layout.decode(&layout.locate("a[1]", &mut st).unwrap(), &mut st).unwrap(), json!("2"));
+        assert!(layout.locate("a[2]", &mut st).unwrap_err().contains("out of bounds"));
+        assert!(layout.locate(&format!("m[{key}].nope"), &mut st).is_err());
+    }
+}
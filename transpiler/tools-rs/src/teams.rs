@@ -0,0 +1,139 @@
+//! Registered teams as the team exporter writes them: one [`Team`] per live registry slot, its
+//! mons in team order with names resolved, serialized as JSON or flattened to CSV (one row per
+//! mon) for spreadsheets and matchmaking scripts.
+
+use alloy_primitives::Address;
+use chomp_bindings::MonStats;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    pub hp: u32,
+    pub stamina: u32,
+    pub speed: u32,
+    pub attack: u32,
+    pub defense: u32,
+    pub special_attack: u32,
+    pub special_defense: u32,
+    pub type1: String,
+    pub type2: String,
+}
+
+impl From<&MonStats> for Stats {
+    fn from(s: &MonStats) -> Stats {
+        Stats {
+            hp: s.hp,
+            stamina: s.stamina,
+            speed: s.speed,
+            attack: s.attack,
+            defense: s.defense,
+            special_attack: s.specialAttack,
+            special_defense: s.specialDefense,
+            type1: format!("{:?}", s.type1),
+            type2: format!("{:?}", s.type2),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamMon {
+    pub mon_id: u64,
+    pub name: String,
+    pub ability: String,
+    pub moves: Vec<String>,
+    /// Battle stats, facets applied.
+    pub stats: Stats,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Team {
+    pub player: Address,
+    /// The registry slot (`teamIndex` in a battle proposal), not the display position.
+    pub slot: u64,
+    pub mons: Vec<TeamMon>,
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// One row per mon; `move0..moveN-1` columns sized to the widest loadout.
+pub fn to_csv(teams: &[Team]) -> String {
+    let lanes = teams.iter().flat_map(|t| &t.mons).map(|m| m.moves.len()).max().unwrap_or(0);
+    let mut header: Vec<String> =
+        ["player", "slot", "position", "monId", "mon", "ability"].iter().map(|s| s.to_string()).collect();
+    header.extend((0..lanes).map(|i| format!("move{i}")));
+    header.extend(
+        ["hp", "stamina", "speed", "attack", "defense", "specialAttack", "specialDefense", "type1", "type2"]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    let mut out = header.join(",") + "\n";
+    for t in teams {
+        for (position, m) in t.mons.iter().enumerate() {
+            let s = &m.stats;
+            let mut row = vec![t.player.to_string(), t.slot.to_string(), position.to_string(), m.mon_id.to_string()];
+            row.extend([m.name.clone(), m.ability.clone()]);
+            row.extend((0..lanes).map(|i| m.moves.get(i).cloned().unwrap_or_default()));
+            row.extend(
+                [s.hp, s.stamina, s.speed, s.attack, s.defense, s.special_attack, s.special_defense]
+                    .map(|v| v.to_string()),
+            );
+            row.extend([s.type1.clone(), s.type2.clone()]);
+            out += &(row.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(",") + "\n");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chomp_bindings::Type;
+
+    #[test]
+    fn csv_flattens_one_row_per_mon() {
+        let stats = MonStats {
+            hp: 303,
+            stamina: 5,
+            speed: 181,
+            attack: 157,
+            defense: 202,
+            specialAttack: 151,
+            specialDefense: 202,
+            type1: Type::Yin,
+            type2: Type::Fire,
+        };
+        let mon = |mon_id, name: &str, moves: &[&str]| TeamMon {
+            mon_id,
+            name: name.into(),
+            ability: "Rise From The Grave".into(),
+            moves: moves.iter().map(|m| m.to_string()).collect(),
+            stats: Stats::from(&stats),
+        };
+        let team = Team {
+            player: Address::repeat_byte(0xa0),
+            slot: 3,
+            mons: vec![
+                mon(0, "Ghouliath", &["Eternal Grudge", "Wither Away"]),
+                mon(7, "Say \"hi\", Mon", &["Osteoporosis"]),
+            ],
+        };
+        let csv = to_csv(&[team]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("player,slot,position,monId,mon,ability,move0,move1,hp,"));
+        assert_eq!(lines[0].split(',').count(), lines[1].split(',').count());
+        let player = Address::repeat_byte(0xa0);
+        let row = "3,0,0,Ghouliath,Rise From The Grave,Eternal Grudge,Wither Away,303,5,181,157,202,151,202,Yin,Fire";
+        assert_eq!(lines[1], format!("{player},{row}"));
+        assert!(lines[2].contains(",1,7,\"Say \"\"hi\"\", Mon\",Rise From The Grave,Osteoporosis,,303,"));
+    }
+}
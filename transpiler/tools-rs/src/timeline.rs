@@ -0,0 +1,307 @@
+//! Engine event decoding and per-block state diffs — the pieces the log decoder stitches into a
+//! battle timeline.
+//!
+//! The engine logs moves, not outcomes: `MonMoves` (legacy per-turn execute), `MovesSubmitted`
+//! / `SlotMovesSubmitted` (built-in dual-signed buffer) and the batched completion payloads carry
+//! who did what, but damage and effect changes are never emitted. Those come from diffing the
+//! `getBattle` view before and after each block that moved the battle ([`Snapshot::diff`]); two
+//! turns executed in one block therefore show as one combined change set.
+
+use alloy_primitives::{Address, U256};
+use chomp_bindings::{BattleConfigView, BattleData, EffectInstance, MonState};
+use chomp_engine::Constants::{
+    CLEARED_MON_STATE_SENTINEL, MOVE_INDEX_OFFSET, NO_OP_MOVE_INDEX, SWITCH_MOVE_INDEX, TOMBSTONE_ADDRESS,
+};
+
+/// One player's turn input with the move index RAW (0-based slot, `SWITCH_MOVE_INDEX`,
+/// `NO_OP_MOVE_INDEX`) — the form players submit and `MovesSubmitted` publishes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Decision {
+    pub move_index: u8,
+    pub extra_data: u16,
+    pub salt: u128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Move(u8),
+    Switch(u16),
+    NoOp,
+}
+
+impl Decision {
+    pub fn action(&self) -> Action {
+        match self.move_index {
+            SWITCH_MOVE_INDEX => Action::Switch(self.extra_data),
+            NO_OP_MOVE_INDEX => Action::NoOp,
+            m => Action::Move(m),
+        }
+    }
+}
+
+fn bits(word: U256, shift: usize, width: usize) -> u128 {
+    ((word >> shift) & ((U256::from(1u8) << width) - U256::from(1u8))).to::<u128>()
+}
+
+/// `MovesSubmitted.packed` / a buffer word / a batch entry: p0 in the low 128 bits, p1 in the
+/// high, each `[move 8 | extraData 16 | salt 104]` from the bottom.
+pub fn decode_turn_word(word: U256) -> [Decision; 2] {
+    let half = |base: usize| Decision {
+        move_index: bits(word, base, 8) as u8,
+        extra_data: bits(word, base + 8, 16) as u16,
+        salt: bits(word, base + 24, 104),
+    };
+    [half(0), half(128)]
+}
+
+/// `MonMoves`: per player the active mon and the STORED move (real-turn bit set, move slots
+/// offset by one); a zero lane means that player did not act this turn.
+pub fn decode_mon_moves(packed_moves: U256, packed_salts: U256) -> [Option<(u8, Decision)>; 2] {
+    let lane = |p: usize| {
+        let stored = bits(packed_moves, 32 * p + 8, 8) as u8;
+        if stored == 0 {
+            return None;
+        }
+        let m = stored & 0x7f;
+        let move_index = if m < SWITCH_MOVE_INDEX { m - MOVE_INDEX_OFFSET } else { m };
+        let decision = Decision {
+            move_index,
+            extra_data: bits(packed_moves, 32 * p + 16, 16) as u16,
+            salt: bits(packed_salts, 104 * p, 104),
+        };
+        Some((bits(packed_moves, 32 * p, 8) as u8, decision))
+    };
+    [lane(0), lane(1)]
+}
+
+/// `BattleCompleteWithBatchTurns.payload`: the winner, then 19 bytes per turn (the low 152 bits
+/// of each entry; the CPU's p1 salt is dropped and reads back as 0).
+pub fn decode_batch_payload(payload: &[u8]) -> Option<(Address, Vec<[Decision; 2]>)> {
+    decode_batch(payload, 19)
+        .map(|(w, turns)| (w, turns.iter().map(|t| decode_turn_word(U256::from_be_slice(t))).collect()))
+}
+
+/// A 2-slot side word: `[m0 8 | e0 16 | m1 8 | e1 16 | salt]` from the bottom, moves raw.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SideTurn {
+    pub slots: [(u8, u16); 2],
+    pub salt: u128,
+}
+
+pub fn decode_side_word(word: U256) -> SideTurn {
+    SideTurn {
+        slots: [
+            (bits(word, 0, 8) as u8, bits(word, 8, 16) as u16),
+            (bits(word, 24, 8) as u8, bits(word, 32, 16) as u16),
+        ],
+        salt: bits(word, 48, 80),
+    }
+}
+
+/// `BattleCompleteWithBatchSlotTurns.payload`: the winner, then 25 bytes per turn — side 0's low
+/// 152 bits and side 1's low 48 (its salt dropped).
+pub fn decode_batch_slot_payload(payload: &[u8]) -> Option<(Address, Vec<[SideTurn; 2]>)> {
+    decode_batch(payload, 25).map(|(w, turns)| {
+        let sides = turns.iter().map(|t| {
+            [decode_side_word(U256::from_be_slice(&t[..19])), decode_side_word(U256::from_be_slice(&t[19..]))]
+        });
+        (w, sides.collect())
+    })
+}
+
+fn decode_batch(payload: &[u8], stride: usize) -> Option<(Address, Vec<&[u8]>)> {
+    if payload.len() < 20 || !(payload.len() - 20).is_multiple_of(stride) {
+        return None;
+    }
+    Some((Address::from_slice(&payload[..20]), payload[20..].chunks(stride).collect()))
+}
+
+/// The slice of `getBattle` a timeline diffs between blocks.
+#[derive(Clone, Debug, Default)]
+pub struct Snapshot {
+    pub turn_id: u16,
+    pub winner_index: u8,
+    /// `active[side][slot]`: one slot in singles, two in 2-slot mode (`activeMonExt`).
+    pub active: [Vec<usize>; 2],
+    /// `states[side][mon]`.
+    pub states: [Vec<MonState>; 2],
+    /// `effects[side][mon]`, tombstones dropped.
+    pub effects: [Vec<Vec<Address>>; 2],
+    pub global_effects: Vec<Address>,
+    /// `teams[side][mon]` move slot words, for naming the moves in a turn.
+    pub moves: [Vec<Vec<U256>>; 2],
+}
+
+fn live(list: &[EffectInstance]) -> Vec<Address> {
+    let tomb = Address::from_slice(TOMBSTONE_ADDRESS.as_slice());
+    list.iter().map(|e| e.effect).filter(|a| *a != tomb && !a.is_zero()).collect()
+}
+
+/// A stat delta as the engine reports it, with the cleared-state sentinel read back as zero.
+fn delta(v: i32) -> i32 {
+    if v == CLEARED_MON_STATE_SENTINEL {
+        0
+    } else {
+        v
+    }
+}
+
+/// One line of a diff; effect changes with `side: None` are global.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    Stat { side: usize, mon: usize, stat: &'static str, before: i32, after: i32 },
+    KnockedOut { side: usize, mon: usize },
+    EffectAdded { side: Option<usize>, mon: usize, effect: Address },
+    EffectRemoved { side: Option<usize>, mon: usize, effect: Address },
+    ActiveSwitched { side: usize, slot: usize, from: usize, to: usize },
+}
+
+impl Snapshot {
+    pub fn from_view(config: &BattleConfigView, data: &BattleData) -> Snapshot {
+        let side = |i: usize| config.monStates.get(i).cloned().unwrap_or_default();
+        let fx = |list: &[Vec<EffectInstance>]| list.iter().map(|m| live(m)).collect::<Vec<_>>();
+        let moves =
+            |i: usize| config.teams.get(i).map(|t| t.iter().map(|m| m.moves.clone()).collect()).unwrap_or_default();
+        let active = |side: usize| {
+            let lane = |packed: u16| (packed >> (8 * side)) as u8 as usize;
+            let mut slots = vec![lane(data.activeMonIndex)];
+            if data.isTwoSlotMode {
+                slots.push(lane(data.activeMonExt));
+            }
+            slots
+        };
+        Snapshot {
+            turn_id: data.turnId,
+            winner_index: data.winnerIndex,
+            active: [active(0), active(1)],
+            states: [side(0), side(1)],
+            effects: [fx(&config.p0Effects), fx(&config.p1Effects)],
+            global_effects: live(&config.globalEffects),
+            moves: [moves(0), moves(1)],
+        }
+    }
+
+    /// Everything that changed from `self` to `after`, in a stable order: switches, then per
+    /// side and mon the stat deltas, KOs and effect churn, then global effects.
+    pub fn diff(&self, after: &Snapshot) -> Vec<Change> {
+        let mut out = Vec::new();
+        for side in 0..2 {
+            for (slot, (&from, &to)) in self.active[side].iter().zip(&after.active[side]).enumerate() {
+                if from != to {
+                    out.push(Change::ActiveSwitched { side, slot, from, to });
+                }
+            }
+        }
+        for side in 0..2 {
+            let default = MonState::default();
+            for mon in 0..after.states[side].len() {
+                let (b, a) = (self.states[side].get(mon).unwrap_or(&default), &after.states[side][mon]);
+                let stats: [(&'static str, i32, i32); 7] = [
+                    ("hp", b.hpDelta, a.hpDelta),
+                    ("stamina", b.staminaDelta, a.staminaDelta),
+                    ("speed", b.speedDelta, a.speedDelta),
+                    ("attack", b.attackDelta, a.attackDelta),
+                    ("defence", b.defenceDelta, a.defenceDelta),
+                    ("sp.attack", b.specialAttackDelta, a.specialAttackDelta),
+                    ("sp.defence", b.specialDefenceDelta, a.specialDefenceDelta),
+                ];
+                for (stat, before, after) in stats {
+                    if delta(before) != delta(after) {
+                        out.push(Change::Stat { side, mon, stat, before: delta(before), after: delta(after) });
+                    }
+                }
+                if a.isKnockedOut && !b.isKnockedOut {
+                    out.push(Change::KnockedOut { side, mon });
+                }
+                let empty = Vec::new();
+                let (fb, fa) =
+                    (self.effects[side].get(mon).unwrap_or(&empty), after.effects[side].get(mon).unwrap_or(&empty));
+                effect_churn(&mut out, Some(side), mon, fb, fa);
+            }
+        }
+        effect_churn(&mut out, None, 0, &self.global_effects, &after.global_effects);
+        out
+    }
+}
+
+/// Multiset difference, so a second stack of the same effect still shows.
+fn effect_churn(out: &mut Vec<Change>, side: Option<usize>, mon: usize, before: &[Address], after: &[Address]) {
+    let mut left = before.to_vec();
+    for &e in after {
+        match left.iter().position(|&b| b == e) {
+            Some(i) => {
+                left.remove(i);
+            }
+            None => out.push(Change::EffectAdded { side, mon, effect: e }),
+        }
+    }
+    out.extend(left.into_iter().map(|effect| Change::EffectRemoved { side, mon, effect }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_every_move_encoding() {
+        // Buffer word: p0 uses move 2 (extra 5, salt 9), p1 switches to mon 3 (salt 1).
+        let word = U256::from(2u8)
+            | (U256::from(5u8) << 8)
+            | (U256::from(9u8) << 24)
+            | (U256::from(SWITCH_MOVE_INDEX) << 128)
+            | (U256::from(3u8) << 136)
+            | (U256::from(1u8) << 152);
+        let [p0, p1] = decode_turn_word(word);
+        assert_eq!((p0.action(), p0.extra_data, p0.salt), (Action::Move(2), 5, 9));
+        assert_eq!((p1.action(), p1.salt), (Action::Switch(3), 1));
+
+        // MonMoves stores the move offset by one with the real-turn bit; p1's empty lane is "did not act".
+        let moves = U256::from(1u8) | (U256::from(0x80u8 | (2 + MOVE_INDEX_OFFSET)) << 8) | (U256::from(7u8) << 16);
+        let salts = U256::from(0xabu8);
+        let [p0, p1] = decode_mon_moves(moves, salts);
+        assert_eq!(p0, Some((1, Decision { move_index: 2, extra_data: 7, salt: 0xab })));
+        assert_eq!(p1, None);
+
+        let mut payload = vec![0x11; 20];
+        payload.extend_from_slice(&word.to_be_bytes::<32>()[13..]);
+        let (winner, turns) = decode_batch_payload(&payload).unwrap();
+        assert_eq!(winner, Address::repeat_byte(0x11));
+        assert_eq!(turns[0][0], decode_turn_word(word)[0]);
+        assert_eq!(turns[0][1].salt, 0, "the batched p1 salt is dropped");
+        assert!(decode_batch_payload(&payload[..30]).is_none());
+
+        let side = U256::from(1u8) | (U256::from(2u8) << 24) | (U256::from(0x1000u16) << 32) | (U256::from(5u8) << 48);
+        assert_eq!(decode_side_word(side), SideTurn { slots: [(1, 0), (2, 0x1000)], salt: 5 });
+    }
+
+    #[test]
+    fn diff_reports_damage_kos_switches_and_effect_churn() {
+        let burn = Address::repeat_byte(0xb0);
+        let weather = Address::repeat_byte(0x3e);
+        let mut before = Snapshot {
+            active: [vec![0], vec![0]],
+            states: [vec![MonState::default(); 2], vec![MonState::default()]],
+            effects: [vec![vec![], vec![]], vec![vec![burn]]],
+            ..Default::default()
+        };
+        before.states[0][0].hpDelta = CLEARED_MON_STATE_SENTINEL;
+        let mut after = before.clone();
+        after.active[0] = vec![1];
+        after.states[0][0].hpDelta = -40;
+        after.states[0][0].isKnockedOut = true;
+        after.effects[0][1] = vec![burn];
+        after.effects[1][0] = vec![];
+        after.global_effects = vec![weather];
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                Change::ActiveSwitched { side: 0, slot: 0, from: 0, to: 1 },
+                Change::Stat { side: 0, mon: 0, stat: "hp", before: 0, after: -40 },
+                Change::KnockedOut { side: 0, mon: 0 },
+                Change::EffectAdded { side: Some(0), mon: 1, effect: burn },
+                Change::EffectRemoved { side: Some(1), mon: 0, effect: burn },
+                Change::EffectAdded { side: None, mon: 0, effect: weather },
+            ]
+        );
+    }
+}